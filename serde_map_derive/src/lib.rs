@@ -0,0 +1,132 @@
+//! Derive macro for `serde_map` strategies, use it through the `derive` feature of `serde_map`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, DeriveInput, Error, LitStr, Path, Type};
+
+/// Implements `BaseStrategy`, `SerStrategy` and `DeStrategy` for a (usually newtype) key type,
+/// which is stored as itself (`Des = Self`) and serialized as `String`.
+/// By default the value is serialized with `Display` and deserialized with `FromStr`,
+/// which can be replaced with functions:
+/// - `#[serde_map(ser = "Type")]` serialized type instead of `String`, requires `from` and `into`
+/// - `#[serde_map(from = "path")]` `fn(Ser) -> Result<Self, impl Display>`
+/// - `#[serde_map(into = "path")]` `fn(&Self) -> Ser`
+///
+/// The strategy instance is stored in the map, so deserialization requires `Default`.
+/// ```rust
+/// use serde_map::{SerdeMap, SerdeMapStrategy};
+///
+/// #[derive(Debug, Default, PartialEq, SerdeMapStrategy)]
+/// struct UserId(u64);
+///
+/// impl std::fmt::Display for UserId {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         write!(f, "user-{}", self.0)
+///     }
+/// }
+///
+/// impl std::str::FromStr for UserId {
+///     type Err = &'static str;
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         let id = s.strip_prefix("user-").ok_or("expected a `user-` prefix")?;
+///         id.parse().map(Self).map_err(|_| "invalid id")
+///     }
+/// }
+///
+/// #[derive(Debug, Default, PartialEq, SerdeMapStrategy)]
+/// #[serde_map(ser = "u64", from = "Level::from_raw", into = "Level::raw")]
+/// struct Level(u8);
+///
+/// impl Level {
+///     fn from_raw(raw: u64) -> Result<Self, &'static str> {
+///         u8::try_from(raw).map(Self).map_err(|_| "level is too large")
+///     }
+///
+///     fn raw(&self) -> u64 {
+///         self.0.into()
+///     }
+/// }
+///
+/// let json = r#"{"user-1":2}"#;
+/// let map: SerdeMap<String, u8, UserId> = serde_json::from_str(json).unwrap();
+/// assert_eq!(map.0, vec![(UserId(1), 2)]);
+/// assert_eq!(serde_json::to_string(&map).unwrap(), json);
+///
+/// let json = r#"{"1":2}"#;
+/// let map: SerdeMap<u64, u8, Level> = serde_json::from_str(json).unwrap();
+/// assert_eq!(map.0, vec![(Level(1), 2)]);
+/// assert_eq!(serde_json::to_string(&map).unwrap(), json);
+/// ```
+#[proc_macro_derive(SerdeMapStrategy, attributes(serde_map))]
+pub fn derive_strategy(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let mut ser: Option<Type> = None;
+    let mut from: Option<Path> = None;
+    let mut into: Option<Path> = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("serde_map"))
+    {
+        attr.parse_nested_meta(|meta| {
+            let value: LitStr = meta.value()?.parse()?;
+            if meta.path.is_ident("ser") {
+                ser = Some(value.parse()?);
+            } else if meta.path.is_ident("from") {
+                from = Some(value.parse()?);
+            } else if meta.path.is_ident("into") {
+                into = Some(value.parse()?);
+            } else {
+                return Err(meta.error("expected `ser`, `from` or `into`"));
+            }
+            Ok(())
+        })?;
+    }
+    if ser.is_some() && (from.is_none() || into.is_none()) {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`ser` requires both `from` and `into`",
+        ));
+    }
+    let ser = ser.unwrap_or_else(|| parse_quote!(::serde_map::__private::String));
+
+    let serialize = match into {
+        Some(into) => quote!(#into(d)),
+        None => quote!(::serde_map::__private::ToString::to_string(d)),
+    };
+    let deserialize = match from {
+        Some(from) => quote!(#from(s)),
+        None => quote!(<Self as ::core::str::FromStr>::from_str(&s)),
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::serde_map::BaseStrategy<#ser> for #ident #ty_generics #where_clause {
+            type Des = Self;
+        }
+
+        impl #impl_generics ::serde_map::SerStrategy<#ser> for #ident #ty_generics #where_clause {
+            type SerRet<'s> = #ser;
+
+            #[inline]
+            fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+                #serialize
+            }
+        }
+
+        impl #impl_generics ::serde_map::DeStrategy<#ser> for #ident #ty_generics #where_clause {
+            #[inline]
+            fn deserialize<E: ::serde_map::__private::de::Error>(s: #ser) -> ::core::result::Result<Self::Des, E> {
+                #deserialize.map_err(E::custom)
+            }
+        }
+    })
+}
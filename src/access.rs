@@ -0,0 +1,220 @@
+//! Inputs accepted by the visitors which aren't generated by `map_impl!`,
+//! the same as by the `SerdeMap` `Deserialize` impl: a map, a sequence of pairs or unit/none.
+
+use crate::deserialize_map_or_seq;
+use ::core::{fmt, marker::PhantomData};
+use serde::de::{Deserialize, DeserializeSeed, Deserializer, Error, MapAccess, SeqAccess, Visitor};
+
+/// Key and value deserialized by the seeds
+type SeedEntry<'de, KSeed, VSeed> = Option<(
+    <KSeed as DeserializeSeed<'de>>::Value,
+    <VSeed as DeserializeSeed<'de>>::Value,
+)>;
+
+/// Entries of a map or of a sequence of pairs
+pub(crate) trait EntryAccess<'de> {
+    type Error: Error;
+
+    fn size_hint(&self) -> Option<usize>;
+
+    fn next_entry_seed<KSeed, VSeed>(
+        &mut self,
+        key: KSeed,
+        value: VSeed,
+    ) -> Result<SeedEntry<'de, KSeed, VSeed>, Self::Error>
+    where
+        KSeed: DeserializeSeed<'de>,
+        VSeed: DeserializeSeed<'de>;
+
+    #[inline]
+    fn next_entry<K, V>(&mut self) -> Result<Option<(K, V)>, Self::Error>
+    where
+        K: Deserialize<'de>,
+        V: Deserialize<'de>,
+    {
+        self.next_entry_seed(PhantomData, PhantomData)
+    }
+}
+
+struct Map<A>(A);
+
+impl<'de, A: MapAccess<'de>> EntryAccess<'de> for Map<A> {
+    type Error = A::Error;
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+
+    #[inline]
+    fn next_entry_seed<KSeed, VSeed>(
+        &mut self,
+        key: KSeed,
+        value: VSeed,
+    ) -> Result<SeedEntry<'de, KSeed, VSeed>, Self::Error>
+    where
+        KSeed: DeserializeSeed<'de>,
+        VSeed: DeserializeSeed<'de>,
+    {
+        self.0.next_entry_seed(key, value)
+    }
+}
+
+struct Seq<A>(A);
+
+impl<'de, A: SeqAccess<'de>> EntryAccess<'de> for Seq<A> {
+    type Error = A::Error;
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+
+    #[inline]
+    fn next_entry_seed<KSeed, VSeed>(
+        &mut self,
+        key: KSeed,
+        value: VSeed,
+    ) -> Result<SeedEntry<'de, KSeed, VSeed>, Self::Error>
+    where
+        KSeed: DeserializeSeed<'de>,
+        VSeed: DeserializeSeed<'de>,
+    {
+        self.0.next_element_seed(PairSeed(key, value))
+    }
+}
+
+/// No entries, for unit and none
+struct Empty<E>(PhantomData<E>);
+
+impl<'de, E: Error> EntryAccess<'de> for Empty<E> {
+    type Error = E;
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(0)
+    }
+
+    #[inline]
+    fn next_entry_seed<KSeed, VSeed>(
+        &mut self,
+        _: KSeed,
+        _: VSeed,
+    ) -> Result<SeedEntry<'de, KSeed, VSeed>, Self::Error>
+    where
+        KSeed: DeserializeSeed<'de>,
+        VSeed: DeserializeSeed<'de>,
+    {
+        Ok(None)
+    }
+}
+
+/// `(key, value)` pair, deserialized with the seeds
+struct PairSeed<KSeed, VSeed>(KSeed, VSeed);
+
+impl<'de, KSeed, VSeed> DeserializeSeed<'de> for PairSeed<KSeed, VSeed>
+where
+    KSeed: DeserializeSeed<'de>,
+    VSeed: DeserializeSeed<'de>,
+{
+    type Value = (KSeed::Value, VSeed::Value);
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'de, KSeed, VSeed> Visitor<'de> for PairSeed<KSeed, VSeed>
+where
+    KSeed: DeserializeSeed<'de>,
+    VSeed: DeserializeSeed<'de>,
+{
+    type Value = (KSeed::Value, VSeed::Value);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a key-value pair")
+    }
+
+    #[inline]
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let k = seq
+            .next_element_seed(self.0)?
+            .ok_or_else(|| Error::invalid_length(0, &"a key-value pair"))?;
+        let v = seq
+            .next_element_seed(self.1)?
+            .ok_or_else(|| Error::invalid_length(1, &"a key-value pair"))?;
+        Ok((k, v))
+    }
+}
+
+/// Collects the entries, whichever input they come from
+pub(crate) trait VisitEntries<'de> {
+    type Value;
+
+    fn visit_entries<A: EntryAccess<'de>>(self, access: A) -> Result<Self::Value, A::Error>;
+}
+
+struct EntriesVisitor<T>(T);
+
+impl<'de, T: VisitEntries<'de>> Visitor<'de> for EntriesVisitor<T> {
+    type Value = T::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map or a sequence of pairs")
+    }
+
+    #[inline]
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        self.0.visit_entries(Map(map))
+    }
+
+    #[inline]
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        self.0.visit_entries(Seq(seq))
+    }
+
+    #[inline]
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        self.0.visit_entries(Empty(PhantomData))
+    }
+
+    #[inline]
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        self.visit_unit()
+    }
+
+    #[inline]
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_entries(deserializer, self.0)
+    }
+}
+
+/// Deserializes a map (or a sequence of pairs in the human-readable formats),
+/// unit and none are an empty map
+#[inline]
+pub(crate) fn deserialize_entries<'de, D, T>(
+    deserializer: D,
+    visitor: T,
+) -> Result<T::Value, D::Error>
+where
+    D: Deserializer<'de>,
+    T: VisitEntries<'de>,
+{
+    deserialize_map_or_seq(deserializer, EntriesVisitor(visitor))
+}
@@ -0,0 +1,132 @@
+use crate::{BaseStrategy, Linear, SerStrategy, SerdeMap};
+use ::core::{fmt, ops::Deref};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+/// Borrowed view of entries and strategy instances, serialized the same way as [`SerdeMap`].
+/// Allows serializing data without moving it into a `SerdeMap`.
+pub struct SerdeMapRef<'a, K, V, KS: BaseStrategy<K> = Linear, VS: BaseStrategy<V> = Linear>(
+    pub &'a [(KS::Des, VS::Des)],
+    &'a (KS, VS),
+);
+
+impl<'a, K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> SerdeMapRef<'a, K, V, KS, VS> {
+    /// Stateless strategies can be passed as a constant, e.g. `&(Linear, Linear)`
+    #[inline(always)]
+    pub fn new(data: &'a [(KS::Des, VS::Des)], strategies: &'a (KS, VS)) -> Self {
+        Self(data, strategies)
+    }
+
+    #[inline(always)]
+    pub fn key_strategy(&self) -> &'a KS {
+        &self.1 .0
+    }
+
+    #[inline(always)]
+    pub fn value_strategy(&self) -> &'a VS {
+        &self.1 .1
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> Clone for SerdeMapRef<'_, K, V, KS, VS> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> Copy for SerdeMapRef<'_, K, V, KS, VS> {}
+
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> fmt::Debug for SerdeMapRef<'_, K, V, KS, VS>
+where
+    KS::Des: fmt::Debug,
+    VS::Des: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SerdeMapRef").field(&self.0).finish()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> Deref for SerdeMapRef<'_, K, V, KS, VS> {
+    type Target = [(KS::Des, VS::Des)];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a, K, V> From<&'a [(K, V)]> for SerdeMapRef<'a, K, V> {
+    #[inline(always)]
+    fn from(data: &'a [(K, V)]) -> Self {
+        Self::new(data, &(Linear, Linear))
+    }
+}
+
+impl<'a, K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> From<&'a SerdeMap<K, V, KS, VS>>
+    for SerdeMapRef<'a, K, V, KS, VS>
+{
+    #[inline(always)]
+    fn from(map: &'a SerdeMap<K, V, KS, VS>) -> Self {
+        Self::new(&map.0, &map.1)
+    }
+}
+
+impl<K, V, KS: SerStrategy<K>, VS: SerStrategy<V>> Serialize for SerdeMapRef<'_, K, V, KS, VS> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let human_readable = serializer.is_human_readable();
+        let (ks, vs) = self.1;
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (k, v) in self.0 {
+            map.serialize_entry(
+                &ks.serialize_format(k, human_readable)?,
+                &vs.serialize_format(v, human_readable)?,
+            )?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::AsPairs;
+    use ::alloc::string::String;
+    use serde::ser::Error;
+
+    struct Prefix(&'static str);
+
+    impl BaseStrategy<String> for Prefix {
+        type Des = String;
+    }
+
+    impl SerStrategy<String> for Prefix {
+        type SerRet<'s> = String;
+        fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+            d.clone()
+        }
+        fn serialize_with<E: Error>(&self, d: &Self::Des) -> Result<String, E> {
+            Ok([self.0, d].concat())
+        }
+    }
+
+    #[test]
+    fn same_as_map() {
+        let mut map: SerdeMap<String, u8, Prefix> = SerdeMap::with_strategies(Prefix("p_"), Linear);
+        map.insert_unchecked("a".into(), 1);
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#"{"p_a":1}"#);
+        assert_eq!(serde_json::to_string(&map.as_map_ref()).unwrap(), json);
+        assert_eq!(
+            serde_json::to_string(&AsPairs(map.as_map_ref())).unwrap(),
+            serde_json::to_string(&AsPairs(&map)).unwrap()
+        );
+
+        let data = [(1, 2)];
+        let view = SerdeMapRef::from(&data[..]);
+        assert_eq!(serde_json::to_string(&view).unwrap(), r#"{"1":2}"#);
+    }
+}
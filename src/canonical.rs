@@ -0,0 +1,59 @@
+use crate::{SerStrategy, SerdeMap};
+use ::alloc::vec::Vec;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+/// Serializes the wrapped [`SerdeMap`] with entries sorted by the serialized key,
+/// without mutating the map. Gives deterministic output for hashing, signing or fixtures.
+/// The sort is stable, so duplicate keys keep their relative order.
+#[derive(Debug)]
+pub struct Canonical<'a, M>(pub &'a M);
+
+impl<M> Clone for Canonical<'_, M> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for Canonical<'_, M> {}
+
+impl<'a, K, V, KS: SerStrategy<K>, VS: SerStrategy<V>> Serialize
+    for Canonical<'a, SerdeMap<K, V, KS, VS>>
+where
+    KS::SerRet<'a>: Ord,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let human_readable = serializer.is_human_readable();
+        let (ks, vs) = (self.0.key_strategy(), self.0.value_strategy());
+        let mut entries = self
+            .0
+            .iter()
+            .map(|(k, v)| Ok((ks.serialize_format(k, human_readable)?, v)))
+            .collect::<Result<Vec<_>, S::Error>>()?;
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+        for (k, v) in entries {
+            map.serialize_entry(&k, &vs.serialize_format(v, human_readable)?)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::alloc::{string::String, vec};
+
+    #[test]
+    fn sorted_keys() {
+        let map: SerdeMap<String, u8> =
+            vec![("b".into(), 1), ("a".into(), 2), ("c".into(), 3)].into();
+        let json = serde_json::to_string(&Canonical(&map)).unwrap();
+        assert_eq!(json, r#"{"a":2,"b":1,"c":3}"#);
+        assert_eq!(map.0[0].0, "b");
+    }
+}
@@ -0,0 +1,451 @@
+//! Helpers for table formats like CSV, which can't express a map field: the keys of the maps
+//! are expanded into columns, and the cells are written with any CSV writer,
+//! or as CSV text by [`to_csv`] (parsed back by [`from_csv`]).
+//! ```rust
+//! use serde_map::{csv, SerdeMap};
+//!
+//! let records: Vec<SerdeMap<String, u8>> = vec![
+//!     SerdeMap::from_iter([("a".into(), 1), ("b".into(), 2)]),
+//!     SerdeMap::from_iter([("c".into(), 3), ("a".into(), 4)]),
+//! ];
+//! let columns = csv::columns(&records);
+//! assert_eq!(columns, ["a", "b", "c"]);
+//! assert_eq!(csv::row(&columns, &records[1]), [Some(&4), None, Some(&3)]);
+//!
+//! let back: SerdeMap<String, u8> = csv::from_row(&columns, [Some(4), None, Some(3)]);
+//! assert_eq!(back.0, [("a".into(), 4), ("c".into(), 3)]);
+//!
+//! let text = csv::to_csv(&records);
+//! assert_eq!(text, "a,b,c\n1,2,\n4,,3\n");
+//! let back: Vec<SerdeMap<String, u8>> = csv::from_csv(&text).unwrap();
+//! assert_eq!(back[1].0, [("a".into(), 4), ("c".into(), 3)]);
+//! ```
+//! Records with a map field are written by [`to_csv_records`], with their own columns first.
+
+use crate::{BaseStrategy, SerdeMap};
+use ::alloc::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use ::core::{convert::Infallible, fmt, iter::repeat, mem::take, str::FromStr};
+
+/// Union of the keys of all maps, in the order they are first seen
+pub fn columns<'a, K, V, KS, VS>(
+    maps: impl IntoIterator<Item = &'a SerdeMap<K, V, KS, VS>>,
+) -> Vec<&'a str>
+where
+    K: 'a,
+    V: 'a,
+    KS: BaseStrategy<K> + 'a,
+    KS::Des: AsRef<str>,
+    VS: BaseStrategy<V> + 'a,
+{
+    let mut seen = BTreeSet::new();
+    let mut columns: Vec<&str> = Vec::new();
+    for map in maps {
+        for (k, _) in map.iter() {
+            let k = k.as_ref();
+            if seen.insert(k) {
+                columns.push(k);
+            }
+        }
+    }
+    columns
+}
+
+/// Cells of the map in the order of `columns`, `None` for the missing keys.
+/// The first entry wins for duplicate keys.
+pub fn row<'a, K, V, KS, VS, C>(
+    columns: &[C],
+    map: &'a SerdeMap<K, V, KS, VS>,
+) -> Vec<Option<&'a VS::Des>>
+where
+    KS: BaseStrategy<K>,
+    KS::Des: AsRef<str>,
+    VS: BaseStrategy<V>,
+    C: AsRef<str>,
+{
+    let mut values = BTreeMap::new();
+    for (k, v) in map.iter() {
+        values.entry(k.as_ref()).or_insert(v);
+    }
+    columns
+        .iter()
+        .map(|c| values.get(c.as_ref()).copied())
+        .collect()
+}
+
+/// Map from the cells of a row, in the order of `columns`, skipping the `None` cells
+pub fn from_row<K, V, KS, VS, C>(
+    columns: &[C],
+    cells: impl IntoIterator<Item = Option<VS::Des>>,
+) -> SerdeMap<K, V, KS, VS>
+where
+    KS: BaseStrategy<K> + Default,
+    KS::Des: From<String>,
+    VS: BaseStrategy<V> + Default,
+    C: AsRef<str>,
+{
+    columns
+        .iter()
+        .zip(cells)
+        .filter_map(|(c, v)| Some((c.as_ref().to_string().into(), v?)))
+        .collect()
+}
+
+/// Invalid CSV text or cell
+#[derive(Debug, Clone)]
+pub struct CsvError(pub String);
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl serde::ser::StdError for CsvError {}
+
+/// Writes a cell, quoted if it's empty or contains a separator, a quote or a line break
+fn write_cell(out: &mut String, cell: &str) {
+    if !cell.is_empty() && !cell.contains([',', '"', '\n', '\r']) {
+        out.push_str(cell);
+        return;
+    }
+    out.push('"');
+    out.push_str(&cell.replace('"', "\"\""));
+    out.push('"');
+}
+
+/// Writes a line of cells, `None` as an empty cell
+fn write_line<T: AsRef<str>>(out: &mut String, cells: impl IntoIterator<Item = Option<T>>) {
+    for (i, cell) in cells.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if let Some(cell) = cell {
+            write_cell(out, cell.as_ref());
+        }
+    }
+    out.push('\n');
+}
+
+/// CSV text with a header of [`columns`] and a line per map (RFC 4180).
+/// The values are written by `Display`, the missing keys as empty cells
+/// (empty values are quoted, so they stay distinct).
+#[inline]
+pub fn to_csv<K, V, KS, VS>(maps: &[SerdeMap<K, V, KS, VS>]) -> String
+where
+    KS: BaseStrategy<K>,
+    KS::Des: AsRef<str>,
+    VS: BaseStrategy<V>,
+    VS::Des: fmt::Display,
+{
+    to_csv_records(maps, &[] as &[&str], |_| [] as [String; 0], |map| map)
+}
+
+/// Same as [`to_csv`], but for records with a map field: the `leading` columns
+/// (with the cells by `fields`, written by `Display`) are followed by the keys of the maps
+/// (projected by `map`). Missing leading cells are written empty, extra ones are dropped.
+/// ```rust
+/// use serde_map::{csv, SerdeMap};
+///
+/// struct Player {
+///     name: String,
+///     stats: SerdeMap<String, u8>,
+/// }
+///
+/// let players = [Player {
+///     name: "a".into(),
+///     stats: SerdeMap::from_iter([("wins".into(), 2)]),
+/// }];
+/// let text = csv::to_csv_records(&players, &["name"], |p| [&p.name], |p| &p.stats);
+/// assert_eq!(text, "name,wins\na,2\n");
+///
+/// let back = csv::from_csv_records(&text, &["name"], |cells, stats: SerdeMap<String, u8>| {
+///     let name = cells[0].clone().ok_or("no name")?;
+///     Ok::<_, &str>(Player { name, stats })
+/// })
+/// .unwrap();
+/// assert_eq!(back[0].stats.0, players[0].stats.0);
+/// ```
+pub fn to_csv_records<'a, R, K, V, KS, VS, C, F>(
+    records: &'a [R],
+    leading: &[C],
+    fields: impl Fn(&'a R) -> F,
+    map: impl Fn(&'a R) -> &'a SerdeMap<K, V, KS, VS>,
+) -> String
+where
+    K: 'a,
+    V: 'a,
+    KS: BaseStrategy<K> + 'a,
+    KS::Des: AsRef<str>,
+    VS: BaseStrategy<V> + 'a,
+    VS::Des: fmt::Display,
+    C: AsRef<str>,
+    F: IntoIterator,
+    F::Item: fmt::Display,
+{
+    let columns = columns(records.iter().map(&map));
+    let mut out = String::new();
+    write_line(
+        &mut out,
+        leading
+            .iter()
+            .map(C::as_ref)
+            .chain(columns.iter().copied())
+            .map(Some),
+    );
+    for record in records {
+        let fields = fields(record)
+            .into_iter()
+            .map(|v| Some(v.to_string()))
+            .chain(repeat(None))
+            .take(leading.len());
+        let cells = row(&columns, map(record))
+            .into_iter()
+            .map(|v| v.map(ToString::to_string));
+        write_line(&mut out, fields.chain(cells));
+    }
+    out
+}
+
+/// Splits CSV text into records, the empty unquoted cells are `None`.
+/// Errors are numbered by the record, from 1 (the header).
+fn records(text: &str) -> Result<Vec<Vec<Option<String>>>, CsvError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut cell = String::new();
+    // the cell was quoted, and it's still open
+    let (mut was_quoted, mut open) = (false, false);
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if open {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    cell.push('"');
+                }
+                '"' => open = false,
+                c => cell.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if cell.is_empty() && !was_quoted => (was_quoted, open) = (true, true),
+            ',' | '\n' => {
+                let cell = take(&mut cell);
+                record.push((take(&mut was_quoted) || !cell.is_empty()).then_some(cell));
+                if c == '\n' {
+                    records.push(take(&mut record));
+                }
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            // a quote inside an unquoted cell, or text after the closing quote
+            c if c == '"' || was_quoted => {
+                return Err(CsvError(format!(
+                    "record {}: unexpected quote",
+                    records.len() + 1
+                )))
+            }
+            c => cell.push(c),
+        }
+    }
+    if open {
+        return Err(CsvError(format!(
+            "record {}: unterminated quote",
+            records.len() + 1
+        )));
+    }
+    if was_quoted || !cell.is_empty() || !record.is_empty() {
+        record.push((was_quoted || !cell.is_empty()).then_some(cell));
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Maps from CSV text with a header line, like the one written by [`to_csv`].
+/// The values are parsed by `FromStr`, the empty cells are skipped.
+#[inline]
+pub fn from_csv<K, V, KS, VS>(text: &str) -> Result<Vec<SerdeMap<K, V, KS, VS>>, CsvError>
+where
+    KS: BaseStrategy<K> + Default,
+    KS::Des: From<String>,
+    VS: BaseStrategy<V> + Default,
+    VS::Des: FromStr,
+    <VS::Des as FromStr>::Err: fmt::Display,
+{
+    from_csv_records(text, &[] as &[&str], |_, map| Ok::<_, Infallible>(map))
+}
+
+/// Records from CSV text, like the one written by [`to_csv_records`]: the header must start
+/// with the `leading` columns, and `record` builds a record from their cells
+/// (`None` for the empty ones) and the map of the rest of the columns.
+pub fn from_csv_records<R, K, V, KS, VS, C, E>(
+    text: &str,
+    leading: &[C],
+    mut record: impl FnMut(Vec<Option<String>>, SerdeMap<K, V, KS, VS>) -> Result<R, E>,
+) -> Result<Vec<R>, CsvError>
+where
+    KS: BaseStrategy<K> + Default,
+    KS::Des: From<String>,
+    VS: BaseStrategy<V> + Default,
+    VS::Des: FromStr,
+    <VS::Des as FromStr>::Err: fmt::Display,
+    C: AsRef<str>,
+    E: fmt::Display,
+{
+    let mut records = records(text)?.into_iter();
+    let mut columns: Vec<String> = records
+        .next()
+        .unwrap_or_default()
+        .into_iter()
+        .map(Option::unwrap_or_default)
+        .collect();
+    for (i, c) in leading.iter().enumerate() {
+        if columns.get(i).map(String::as_str) != Some(c.as_ref()) {
+            return Err(CsvError(format!(
+                "record 1: expected column {:?}",
+                c.as_ref()
+            )));
+        }
+    }
+    let columns = columns.split_off(leading.len());
+    records
+        .zip(2..)
+        .map(|(mut cells, number)| {
+            if cells.len() > leading.len() + columns.len() {
+                return Err(CsvError(format!(
+                    "record {number}: {} cells, but {} columns",
+                    cells.len(),
+                    leading.len() + columns.len()
+                )));
+            }
+            let values = cells.split_off(cells.len().min(leading.len()));
+            cells.resize(leading.len(), None);
+            let values = values.into_iter().zip(&columns).map(|(cell, c)| {
+                cell.map(|v| {
+                    v.parse()
+                        .map_err(|e| CsvError(format!("record {number}, column {c:?}: {e}")))
+                })
+                .transpose()
+            });
+            let map = from_row(&columns, values.collect::<Result<Vec<_>, _>>()?);
+            record(cells, map).map_err(|e| CsvError(format!("record {number}: {e}")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::FromString;
+    use ::alloc::{boxed::Box, vec};
+
+    #[test]
+    fn strategies() {
+        let records: Vec<SerdeMap<String, u8, FromString<Box<str>>>> = vec![
+            SerdeMap::from_iter([("a".into(), 1), ("b".into(), 2), ("a".into(), 5)]),
+            SerdeMap::from_iter([("b".into(), 3)]),
+        ];
+        let columns = columns(&records);
+        assert_eq!(columns, ["a", "b"]);
+        assert_eq!(row(&columns, &records[0]), [Some(&1), Some(&2)]);
+
+        let back: SerdeMap<String, u8, FromString<Box<str>>> = from_row(&columns, [None, Some(3)]);
+        assert_eq!(back.0, records[1].0);
+    }
+
+    #[test]
+    fn csv_text() {
+        let records: Vec<SerdeMap<String, String>> = vec![
+            SerdeMap::from_iter([
+                ("name".into(), "a, \"b\"".into()),
+                ("note".into(), "".into()),
+            ]),
+            SerdeMap::from_iter([("x\ny".into(), "1".into())]),
+        ];
+        let text = to_csv(&records);
+        assert_eq!(text, "name,note,\"x\ny\"\n\"a, \"\"b\"\"\",\"\",\n,,1\n");
+        let back: Vec<SerdeMap<String, String>> = from_csv(&text).unwrap();
+        assert_eq!(back[0].0, records[0].0);
+        assert_eq!(back[1].0, records[1].0);
+
+        let crlf: Vec<SerdeMap<String, u8>> = from_csv("a,b\r\n1,2\r\n,3").unwrap();
+        assert_eq!(crlf[0].0, [("a".into(), 1), ("b".into(), 2)]);
+        assert_eq!(crlf[1].0, [("b".into(), 3)]);
+    }
+
+    #[test]
+    fn csv_errors() {
+        let err = |text| {
+            from_csv::<String, u8, crate::Linear, crate::Linear>(text)
+                .unwrap_err()
+                .0
+        };
+        assert_eq!(err("a\n\"1"), "record 2: unterminated quote");
+        assert_eq!(err("a\n1\""), "record 2: unexpected quote");
+        assert_eq!(err("a\n\"1\"2"), "record 2: unexpected quote");
+        assert_eq!(err("\"a\"b\n1"), "record 1: unexpected quote");
+        assert_eq!(err("a\n1,2"), "record 2: 2 cells, but 1 columns");
+        assert!(err("a\n1\nx").starts_with(r#"record 3, column "a": "#));
+    }
+
+    #[test]
+    fn csv_records() {
+        struct Player {
+            id: u32,
+            name: Option<String>,
+            stats: SerdeMap<String, u8>,
+        }
+
+        let players = [
+            Player {
+                id: 1,
+                name: Some("a, b".into()),
+                stats: SerdeMap::from_iter([("wins".into(), 2)]),
+            },
+            Player {
+                id: 2,
+                name: None,
+                stats: SerdeMap::from_iter([("wins".into(), 3), ("losses".into(), 1)]),
+            },
+        ];
+        let text = to_csv_records(
+            &players,
+            &["id", "name"],
+            |p| {
+                [Some(p.id.to_string()), p.name.clone()]
+                    .into_iter()
+                    .flatten()
+            },
+            |p| &p.stats,
+        );
+        assert_eq!(text, "id,name,wins,losses\n1,\"a, b\",2,\n2,,3,1\n");
+
+        let back = from_csv_records(&text, &["id", "name"], |mut cells, stats| {
+            let id = cells[0].take().ok_or("no id")?;
+            let id = id.parse().map_err(|_| "bad id")?;
+            Ok::<_, &str>(Player {
+                id,
+                name: cells[1].take(),
+                stats,
+            })
+        })
+        .unwrap();
+        assert_eq!(back.len(), 2);
+        for (back, player) in back.iter().zip(&players) {
+            assert_eq!(back.id, player.id);
+            assert_eq!(back.name, player.name);
+            assert_eq!(back.stats.0, player.stats.0);
+        }
+
+        let record =
+            |cells: Vec<Option<String>>, _: SerdeMap<String, u8>| cells[0].clone().ok_or("no id");
+        let err = from_csv_records(",wins\n,1", &["id"], record).unwrap_err();
+        assert_eq!(err.0, "record 1: expected column \"id\"");
+        let err = from_csv_records("id,wins\n,1", &["id"], record).unwrap_err();
+        assert_eq!(err.0, "record 2: no id");
+    }
+}
@@ -0,0 +1,284 @@
+use crate::{entry_error, BaseStrategy, DeStrategy, Linear, SerStrategy, SerdeMap};
+use ::alloc::{
+    collections::{btree_map, BTreeMap},
+    vec::Vec,
+};
+use ::core::{
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+use serde::{
+    de::{Deserialize, Deserializer, Error},
+    ser::{Serialize, Serializer},
+};
+
+/// Policy for repeated keys, applied by [`DedupSerdeMap`] after deserialization.
+/// `positions` are the indices of the entries in the input,
+/// which differ from their indices in `entries` if the strategies skipped some.
+pub trait DuplicateKeys {
+    fn dedup<K: Ord, V, E: Error>(entries: &mut Vec<(K, V)>, positions: &[usize]) -> Result<(), E>;
+}
+
+/// Keeps every entry (the same as plain [`SerdeMap`])
+#[derive(Debug, Clone, Copy)]
+pub struct AllowDuplicates;
+
+/// Keeps the first value of a repeated key
+#[derive(Debug, Clone, Copy)]
+pub struct KeepFirst;
+
+/// Keeps the last value of a repeated key, at the position of the first occurrence
+#[derive(Debug, Clone, Copy)]
+pub struct KeepLast;
+
+/// Fails the deserialization on a repeated key
+#[derive(Debug, Clone, Copy)]
+pub struct DenyDuplicates;
+
+/// Moves the first occurrence of every key into the result,
+/// calling `on_duplicate(position, first_value, value)` for the repeated ones.
+/// The first occurrences are found through a `BTreeMap`, so it's O(n log n).
+fn dedup_with<K: Ord, V, E>(
+    entries: &mut Vec<(K, V)>,
+    positions: &[usize],
+    mut on_duplicate: impl FnMut(usize, &mut V, V) -> Result<(), E>,
+) -> Result<(), E> {
+    // index of the first occurrence in the result, for the repeated keys
+    let mut seen = BTreeMap::new();
+    let firsts: Vec<Option<usize>> = entries
+        .iter()
+        .map(|(k, _)| {
+            let len = seen.len();
+            match seen.entry(k) {
+                btree_map::Entry::Vacant(e) => {
+                    e.insert(len);
+                    None
+                }
+                btree_map::Entry::Occupied(e) => Some(*e.get()),
+            }
+        })
+        .collect();
+
+    let mut kept: Vec<(K, V)> = Vec::with_capacity(seen.len());
+    let entries_with_firsts = ::core::mem::take(entries).into_iter().zip(firsts);
+    for (index, ((k, v), first)) in entries_with_firsts.enumerate() {
+        match first {
+            Some(i) => on_duplicate(
+                positions.get(index).map_or(index, |p| *p),
+                &mut kept[i].1,
+                v,
+            )?,
+            None => kept.push((k, v)),
+        }
+    }
+    *entries = kept;
+    Ok(())
+}
+
+impl DuplicateKeys for AllowDuplicates {
+    #[inline(always)]
+    fn dedup<K: Ord, V, E: Error>(_: &mut Vec<(K, V)>, _: &[usize]) -> Result<(), E> {
+        Ok(())
+    }
+}
+
+impl DuplicateKeys for KeepFirst {
+    #[inline]
+    fn dedup<K: Ord, V, E: Error>(entries: &mut Vec<(K, V)>, positions: &[usize]) -> Result<(), E> {
+        dedup_with(entries, positions, |_, _, _| Ok(()))
+    }
+}
+
+impl DuplicateKeys for KeepLast {
+    #[inline]
+    fn dedup<K: Ord, V, E: Error>(entries: &mut Vec<(K, V)>, positions: &[usize]) -> Result<(), E> {
+        dedup_with(entries, positions, |_, first, v| {
+            *first = v;
+            Ok(())
+        })
+    }
+}
+
+impl DuplicateKeys for DenyDuplicates {
+    #[inline]
+    fn dedup<K: Ord, V, E: Error>(entries: &mut Vec<(K, V)>, positions: &[usize]) -> Result<(), E> {
+        dedup_with(entries, positions, |position, _, _| {
+            Err(entry_error(position, E::custom("duplicate key")))
+        })
+    }
+}
+
+/// Key strategy which also stores the position of the entry in the input.
+/// Only `deserialize_format` is called by `SerdeMap`, so `deserialize` reports 0.
+#[derive(Default)]
+struct Positioned<KS>(KS);
+
+impl<K, KS: BaseStrategy<K>> BaseStrategy<K> for Positioned<KS> {
+    type Des = (usize, KS::Des);
+}
+
+impl<K, KS: DeStrategy<K>> DeStrategy<K> for Positioned<KS> {
+    #[inline]
+    fn deserialize<E: Error>(s: K) -> Result<Self::Des, E> {
+        KS::deserialize(s).map(|k| (0, k))
+    }
+
+    #[inline]
+    fn deserialize_format<E: Error>(
+        &self,
+        index: usize,
+        s: K,
+        human_readable: bool,
+    ) -> Result<Option<Self::Des>, E> {
+        let k = self.0.deserialize_format(index, s, human_readable)?;
+        Ok(k.map(|k| (index, k)))
+    }
+}
+
+/// [`SerdeMap`] which applies the [`DuplicateKeys`] policy `P` during deserialization.
+/// Serialized the same way as the inner `SerdeMap`.
+pub struct DedupSerdeMap<
+    K,
+    V,
+    P = KeepLast,
+    KS: BaseStrategy<K> = Linear,
+    VS: BaseStrategy<V> = Linear,
+>(pub SerdeMap<K, V, KS, VS>, PhantomData<P>);
+
+impl<K, V, P, KS: BaseStrategy<K>, VS: BaseStrategy<V>> DedupSerdeMap<K, V, P, KS, VS> {
+    #[inline(always)]
+    pub fn into_inner(self) -> SerdeMap<K, V, KS, VS> {
+        self.0
+    }
+}
+
+impl<K, V, P, KS: BaseStrategy<K>, VS: BaseStrategy<V>> Clone for DedupSerdeMap<K, V, P, KS, VS>
+where
+    SerdeMap<K, V, KS, VS>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<K, V, P, KS: BaseStrategy<K>, VS: BaseStrategy<V>> fmt::Debug
+    for DedupSerdeMap<K, V, P, KS, VS>
+where
+    SerdeMap<K, V, KS, VS>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DedupSerdeMap").field(&self.0).finish()
+    }
+}
+
+impl<K, V, P, KS: BaseStrategy<K> + Default, VS: BaseStrategy<V> + Default> Default
+    for DedupSerdeMap<K, V, P, KS, VS>
+{
+    #[inline]
+    fn default() -> Self {
+        Self(SerdeMap::new(), PhantomData)
+    }
+}
+
+impl<K, V, P, KS: BaseStrategy<K>, VS: BaseStrategy<V>> Deref for DedupSerdeMap<K, V, P, KS, VS> {
+    type Target = SerdeMap<K, V, KS, VS>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<K, V, P, KS: BaseStrategy<K>, VS: BaseStrategy<V>> DerefMut
+    for DedupSerdeMap<K, V, P, KS, VS>
+{
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<K, V, P, KS: BaseStrategy<K>, VS: BaseStrategy<V>> From<SerdeMap<K, V, KS, VS>>
+    for DedupSerdeMap<K, V, P, KS, VS>
+{
+    #[inline(always)]
+    fn from(map: SerdeMap<K, V, KS, VS>) -> Self {
+        Self(map, PhantomData)
+    }
+}
+
+impl<K, V, P, KS: SerStrategy<K>, VS: SerStrategy<V>> Serialize for DedupSerdeMap<K, V, P, KS, VS>
+where
+    SerdeMap<K, V, KS, VS>: Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, K, V, P, KS, VS> Deserialize<'de> for DedupSerdeMap<K, V, P, KS, VS>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    P: DuplicateKeys,
+    KS: DeStrategy<K> + Default,
+    VS: DeStrategy<V> + Default,
+    KS::Des: Ord,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let SerdeMap(data, (ks, vs)) =
+            SerdeMap::<K, V, Positioned<KS>, VS>::deserialize(deserializer)?;
+        let (positions, mut entries): (Vec<_>, Vec<_>) =
+            data.into_iter().map(|((i, k), v)| (i, (k, v))).unzip();
+        P::dedup(&mut entries, &positions)?;
+        Ok(Self(SerdeMap(entries, (ks.0, vs)), PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::PublicKeys;
+    use ::alloc::{
+        string::{String, ToString},
+        vec,
+    };
+
+    fn values<P: DuplicateKeys>() -> Result<Vec<u8>, serde_json::Error> {
+        let json = r#"{"a":1,"b":2,"a":3}"#;
+        let map: DedupSerdeMap<String, u8, P> = serde_json::from_str(json)?;
+        Ok(map.iter().map(|(_, v)| *v).collect())
+    }
+
+    #[test]
+    fn policies() {
+        assert_eq!(values::<AllowDuplicates>().unwrap(), vec![1, 2, 3]);
+        assert_eq!(values::<KeepFirst>().unwrap(), vec![1, 2]);
+        assert_eq!(values::<KeepLast>().unwrap(), vec![3, 2]);
+        let err = values::<DenyDuplicates>().unwrap_err().to_string();
+        assert!(err.starts_with("entry 2: duplicate key"), "{err}");
+    }
+
+    #[test]
+    fn input_position() {
+        let json = r#"{"_a":0,"b":1,"_c":2,"b":3}"#;
+        let map: DedupSerdeMap<String, u8, KeepLast, PublicKeys> =
+            serde_json::from_str(json).unwrap();
+        assert_eq!(map.0 .0, vec![("b".to_string(), 3)]);
+
+        let err =
+            serde_json::from_str::<DedupSerdeMap<String, u8, DenyDuplicates, PublicKeys>>(json)
+                .unwrap_err()
+                .to_string();
+        assert!(err.starts_with("entry 3: duplicate key"), "{err}");
+    }
+}
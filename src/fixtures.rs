@@ -0,0 +1,22 @@
+//! Strategies shared by the tests
+
+use crate::{BaseStrategy, DeStrategy};
+use ::alloc::string::String;
+use serde::de::Error;
+
+/// Skips the keys starting with `_`
+#[derive(Debug, Default)]
+pub(crate) struct PublicKeys;
+
+impl BaseStrategy<String> for PublicKeys {
+    type Des = String;
+}
+
+impl DeStrategy<String> for PublicKeys {
+    fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+        Ok(s)
+    }
+    fn deserialize_filter<E: Error>(&self, _: usize, s: String) -> Result<Option<String>, E> {
+        Ok(Some(s).filter(|s| !s.starts_with('_')))
+    }
+}
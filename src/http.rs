@@ -0,0 +1,95 @@
+use crate::{HeaderKey, SerdeMap};
+use ::http::{header::ToStrError, HeaderMap, HeaderName, HeaderValue};
+use ::std::string::{String, ToString};
+
+/// Every value of the repeated headers is kept as a separate entry
+impl From<&HeaderMap> for SerdeMap<String, HeaderValue, HeaderKey> {
+    #[inline]
+    fn from(headers: &HeaderMap) -> Self {
+        headers
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+}
+
+/// Repeated keys are appended, so all of their values are kept
+impl From<SerdeMap<String, HeaderValue, HeaderKey>> for HeaderMap {
+    #[inline]
+    fn from(map: SerdeMap<String, HeaderValue, HeaderKey>) -> Self {
+        let mut headers = HeaderMap::with_capacity(map.len());
+        for (k, v) in map.0 {
+            headers.append(k, v);
+        }
+        headers
+    }
+}
+
+/// Header names are lowercase, fails on the values which aren't visible ASCII
+impl TryFrom<&HeaderMap> for SerdeMap<String, String> {
+    type Error = ToStrError;
+
+    #[inline]
+    fn try_from(headers: &HeaderMap) -> Result<Self, Self::Error> {
+        headers
+            .iter()
+            .map(|(k, v)| Ok((k.as_str().to_string(), v.to_str()?.to_string())))
+            .collect()
+    }
+}
+
+/// Repeated keys (in any case) are appended, so all of their values are kept
+impl TryFrom<SerdeMap<String, String>> for HeaderMap {
+    type Error = ::http::Error;
+
+    #[inline]
+    fn try_from(map: SerdeMap<String, String>) -> Result<Self, Self::Error> {
+        let mut headers = HeaderMap::with_capacity(map.len());
+        for (k, v) in map.0 {
+            headers.append(HeaderName::try_from(k)?, HeaderValue::try_from(v)?);
+        }
+        Ok(headers)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::std::vec;
+
+    #[test]
+    fn header_map_conversions() {
+        let json = r#"{"Accept":"a","Set-Cookie":"x=1","set-cookie":"y=2"}"#;
+        let map: SerdeMap<String, String> = serde_json::from_str(json).unwrap();
+        let headers = HeaderMap::try_from(map).unwrap();
+        let cookies: vec::Vec<_> = headers.get_all("set-cookie").iter().collect();
+        assert_eq!(cookies, ["x=1", "y=2"]);
+
+        let map = SerdeMap::<String, HeaderValue, HeaderKey>::from(&headers);
+        assert_eq!(map.len(), 3);
+        assert_eq!(
+            map.get("set-cookie"),
+            Some(&HeaderValue::from_static("x=1"))
+        );
+        assert_eq!(HeaderMap::from(map), headers);
+
+        let map = SerdeMap::<String, String>::try_from(&headers).unwrap();
+        assert_eq!(
+            map.0,
+            vec![
+                ("accept".into(), "a".into()),
+                ("set-cookie".into(), "x=1".into()),
+                ("set-cookie".into(), "y=2".into()),
+            ]
+        );
+
+        let keys: SerdeMap<String, u8, HeaderKey> =
+            serde_json::from_str(r#"{"Content-Type":1}"#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&keys).unwrap(),
+            r#"{"content-type":1}"#
+        );
+        let invalid: SerdeMap<String, String> = SerdeMap::from_iter([("a b".into(), "c".into())]);
+        assert!(HeaderMap::try_from(invalid).is_err());
+    }
+}
@@ -0,0 +1,287 @@
+use crate::{BaseStrategy, DeStrategy, Entries, Linear, SerStrategy, SerdeMap};
+use ::alloc::vec::Vec;
+use ::core::{borrow::Borrow, hash::Hash, marker::PhantomData, mem, ops::Deref};
+use ::std::collections::HashMap;
+use serde::{
+    de::{Deserialize, Deserializer},
+    ser::{Serialize, SerializeMap, Serializer},
+};
+
+/// `Map` based on `Vec`, which also maintains a `HashMap` index of the keys,
+/// for O(1) lookups. The insertion order is preserved.
+///
+/// (De)serialization works the same way as for [`SerdeMap`], using the same strategy.
+/// Duplicate keys of the input are kept, the index points to the first entry of a key,
+/// so the lookups return it, as [`SerdeMap::get`] does.
+/// The strategy isn't stored, `KS::default()` is used, so it can't carry state.
+#[derive(Debug, Clone)]
+pub struct IndexedSerdeMap<K, V, KS: BaseStrategy<K> = Linear> {
+    data: Vec<(KS::Des, V)>,
+    index: HashMap<KS::Des, usize>,
+    marker: PhantomData<KS>,
+}
+
+impl<K, V, KS: BaseStrategy<K>> IndexedSerdeMap<K, V, KS> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            index: HashMap::new(),
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            data: Vec::with_capacity(capacity),
+            index: HashMap::with_capacity(capacity),
+            marker: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[(KS::Des, V)] {
+        &self.data
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> Vec<(KS::Des, V)> {
+        self.data
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>> IndexedSerdeMap<K, V, KS>
+where
+    KS::Des: Clone + Eq + Hash,
+{
+    /// Indexes the entries, keeping the duplicate keys
+    fn from_entries(data: Vec<(KS::Des, V)>) -> Self {
+        let mut index = HashMap::with_capacity(data.len());
+        for (i, (k, _)) in data.iter().enumerate() {
+            index.entry(k.clone()).or_insert(i);
+        }
+        Self {
+            data,
+            index,
+            marker: PhantomData,
+        }
+    }
+
+    /// Appends the entry. If the key is already present,
+    /// replaces the value of its first entry in place and returns the old one.
+    #[inline]
+    pub fn insert(&mut self, k: KS::Des, v: V) -> Option<V> {
+        match self.index.get(&k) {
+            Some(&i) => Some(mem::replace(&mut self.data[i].1, v)),
+            None => {
+                self.index.insert(k.clone(), self.data.len());
+                self.data.push((k, v));
+                None
+            }
+        }
+    }
+
+    #[inline]
+    pub fn get_index_of<Q>(&self, k: &Q) -> Option<usize>
+    where
+        KS::Des: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.get(k).copied()
+    }
+
+    #[inline]
+    pub fn get<Q>(&self, k: &Q) -> Option<&V>
+    where
+        KS::Des: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_index_of(k).map(|i| &self.data[i].1)
+    }
+
+    #[inline]
+    pub fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        KS::Des: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_index_of(k).map(|i| &mut self.data[i].1)
+    }
+
+    #[inline]
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        KS::Des: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.contains_key(k)
+    }
+
+    /// Removes the first entry of the key, shifting the following entries to preserve the order.
+    /// It's O(n), because the following entries are reindexed.
+    #[inline]
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        KS::Des: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = self.index.remove(k)?;
+        let (k, v) = self.data.remove(i);
+        for pos in self.index.values_mut() {
+            if *pos > i {
+                *pos -= 1;
+            }
+        }
+        if let Some(next) = self.data[i..].iter().position(|(key, _)| *key == k) {
+            self.index.insert(k, i + next);
+        }
+        Some(v)
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>> Default for IndexedSerdeMap<K, V, KS> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>> Deref for IndexedSerdeMap<K, V, KS> {
+    type Target = [(KS::Des, V)];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>> IntoIterator for IndexedSerdeMap<K, V, KS> {
+    type Item = (KS::Des, V);
+    type IntoIter = ::alloc::vec::IntoIter<Self::Item>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'a, K, V, KS: BaseStrategy<K>> IntoIterator for &'a IndexedSerdeMap<K, V, KS> {
+    type Item = &'a (KS::Des, V);
+    type IntoIter = ::core::slice::Iter<'a, (KS::Des, V)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>> FromIterator<(KS::Des, V)> for IndexedSerdeMap<K, V, KS>
+where
+    KS::Des: Clone + Eq + Hash,
+{
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (KS::Des, V)>>(iter: T) -> Self {
+        Self::from_entries(iter.into_iter().collect())
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> From<SerdeMap<K, V, KS, VS>>
+    for IndexedSerdeMap<K, VS::Des, KS>
+where
+    KS::Des: Clone + Eq + Hash,
+{
+    #[inline]
+    fn from(map: SerdeMap<K, V, KS, VS>) -> Self {
+        Self::from_entries(map.0)
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K> + Default, VS: BaseStrategy<V> + Default>
+    From<IndexedSerdeMap<K, VS::Des, KS>> for SerdeMap<K, V, KS, VS>
+{
+    #[inline]
+    fn from(map: IndexedSerdeMap<K, VS::Des, KS>) -> Self {
+        map.data.into()
+    }
+}
+
+impl<K, V: Serialize, KS: SerStrategy<K> + Default> Serialize for IndexedSerdeMap<K, V, KS> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let human_readable = serializer.is_human_readable();
+        let ks = KS::default();
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (k, v) in self {
+            map.serialize_entry(&ks.serialize_format(k, human_readable)?, v)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, K, V, KS> Deserialize<'de> for IndexedSerdeMap<K, V, KS>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    KS: DeStrategy<K> + Default,
+    KS::Des: Clone + Eq + Hash,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Entries::<K, V, KS>::deserialize(deserializer).map(|e| Self::from_entries(e.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::alloc::{borrow::ToOwned, string::String};
+
+    #[test]
+    fn indexed_remove() {
+        let mut map: IndexedSerdeMap<String, u8> = [("a", 1), ("b", 2), ("c", 3), ("a", 4)]
+            .into_iter()
+            .map(|(k, v)| (k.to_owned(), v))
+            .collect();
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.get("a"), Some(&1));
+        assert_eq!(map.insert("a".to_owned(), 5), Some(1));
+        assert_eq!(map.remove("a"), Some(5));
+        assert_eq!(map.get_index_of("c"), Some(1));
+        assert_eq!(map.get("c"), Some(&3));
+        assert_eq!(map.get_index_of("a"), Some(2));
+        assert_eq!(map.get("a"), Some(&4));
+        assert_eq!(map.remove("a"), Some(4));
+        assert!(!map.contains_key("a"));
+    }
+
+    #[test]
+    fn duplicate_round_trip() {
+        let json = r#"{"a":1,"b":2,"a":3}"#;
+        let map: IndexedSerdeMap<String, u8> = serde_json::from_str(json).unwrap();
+        assert_eq!(map.get("a"), Some(&1));
+        assert_eq!(serde_json::to_string(&map).unwrap(), json);
+
+        let from_map: IndexedSerdeMap<String, u8> =
+            serde_json::from_str::<SerdeMap<String, u8>>(json)
+                .unwrap()
+                .into();
+        assert_eq!(from_map.as_slice(), map.as_slice());
+    }
+}
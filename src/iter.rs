@@ -0,0 +1,262 @@
+use crate::{
+    access::{deserialize_entries, EntryAccess, VisitEntries},
+    DeStrategy, Linear, SerStrategy,
+};
+use ::core::{cell::Cell, fmt, marker::PhantomData};
+use serde::{
+    de::{Deserialize, DeserializeSeed, Deserializer},
+    ser::{Error, Serialize, SerializeMap, Serializer},
+};
+
+/// Serializes entries straight from an iterator as a map, applying the key strategy
+/// `KS::default()`, so they don't have to be buffered into a [`SerdeMap`](crate::SerdeMap) first.
+/// The length hint is taken from `size_hint()` if it's exact.
+#[inline]
+pub fn serialize_iter<'a, K, V, KS, I, S>(iter: I, serializer: S) -> Result<S::Ok, S::Error>
+where
+    K: 'a,
+    V: Serialize + 'a,
+    KS: SerStrategy<K> + Default,
+    KS::Des: 'a,
+    I: Iterator<Item = (&'a KS::Des, &'a V)>,
+    S: Serializer,
+{
+    serialize_entries::<K, V, KS, I, S>(iter, None, &KS::default(), serializer)
+}
+
+/// `len` overrides the exact `size_hint()` of the iterator
+#[inline]
+fn serialize_entries<'a, K, V, KS, I, S>(
+    iter: I,
+    len: Option<usize>,
+    ks: &KS,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    K: 'a,
+    V: Serialize + 'a,
+    KS: SerStrategy<K>,
+    KS::Des: 'a,
+    I: Iterator<Item = (&'a KS::Des, &'a V)>,
+    S: Serializer,
+{
+    let len = len.or(match iter.size_hint() {
+        (lower, Some(upper)) if lower == upper => Some(lower),
+        _ => None,
+    });
+    let human_readable = serializer.is_human_readable();
+    let mut map = serializer.serialize_map(len)?;
+    for (k, v) in iter {
+        map.serialize_entry(&ks.serialize_format(k, human_readable)?, v)?;
+    }
+    map.end()
+}
+
+/// `Serialize` wrapper around [`serialize_iter`], to be passed into `serde_json::to_writer` etc.
+/// The iterator is consumed by the first serialization, serializing it again fails.
+pub struct SerializeIter<K, V, I, KS = Linear> {
+    iter: Cell<Option<I>>,
+    len: Option<usize>,
+    strategy: KS,
+    marker: PhantomData<(K, V)>,
+}
+
+impl<K, V, I, KS: Default> SerializeIter<K, V, I, KS> {
+    #[inline]
+    pub fn new(iter: I) -> Self {
+        Self::with_strategy(iter, KS::default())
+    }
+}
+
+impl<K, V, I, KS> SerializeIter<K, V, I, KS> {
+    /// Keys are serialized through the instance `strategy`, which can carry state
+    #[inline]
+    pub fn with_strategy(iter: I, strategy: KS) -> Self {
+        Self {
+            iter: Cell::new(Some(iter)),
+            len: None,
+            strategy,
+            marker: PhantomData,
+        }
+    }
+
+    /// Number of entries passed to the serializer, overrides the iterator's `size_hint()`
+    #[inline]
+    pub fn length_hint(mut self, len: usize) -> Self {
+        self.len = Some(len);
+        self
+    }
+}
+
+impl<K, V, I, KS> fmt::Debug for SerializeIter<K, V, I, KS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SerializeIter")
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, K, V, I, KS> Serialize for SerializeIter<K, V, I, KS>
+where
+    K: 'a,
+    V: Serialize + 'a,
+    KS: SerStrategy<K>,
+    KS::Des: 'a,
+    I: Iterator<Item = (&'a KS::Des, &'a V)>,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let iter = self
+            .iter
+            .take()
+            .ok_or_else(|| S::Error::custom("the iterator is already consumed"))?;
+        serialize_entries::<K, V, KS, I, S>(iter, self.len, &self.strategy, serializer)
+    }
+}
+
+/// [`DeserializeSeed`] which passes each `(KS::Des, V)` entry to the closure `f`
+/// instead of collecting them, so a map of any size is processed in constant memory.
+/// Entries skipped by the strategy are not passed.
+pub struct ForEachEntry<K, V, F, KS = Linear> {
+    f: F,
+    strategy: KS,
+    human_readable: bool,
+    marker: PhantomData<(K, V)>,
+}
+
+impl<K, V, F, KS: Default> ForEachEntry<K, V, F, KS> {
+    #[inline]
+    pub fn new(f: F) -> Self {
+        Self::with_strategy(f, KS::default())
+    }
+}
+
+impl<K, V, F, KS> ForEachEntry<K, V, F, KS> {
+    /// Keys are deserialized through the instance `strategy`, which can carry state
+    #[inline]
+    pub fn with_strategy(f: F, strategy: KS) -> Self {
+        Self {
+            f,
+            strategy,
+            human_readable: true,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, K, V, F, KS> DeserializeSeed<'de> for ForEachEntry<K, V, F, KS>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    KS: DeStrategy<K>,
+    F: FnMut(KS::Des, V),
+{
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D>(mut self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.human_readable = deserializer.is_human_readable();
+        deserialize_entries(deserializer, self)
+    }
+}
+
+impl<'de, K, V, F, KS> VisitEntries<'de> for ForEachEntry<K, V, F, KS>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    KS: DeStrategy<K>,
+    F: FnMut(KS::Des, V),
+{
+    type Value = ();
+
+    #[inline]
+    fn visit_entries<A: EntryAccess<'de>>(
+        mut self,
+        mut access: A,
+    ) -> Result<Self::Value, A::Error> {
+        let mut index = 0;
+        while let Some((key, value)) = access.next_entry()? {
+            if let Some(key) = self
+                .strategy
+                .deserialize_format(index, key, self.human_readable)?
+            {
+                (self.f)(key, value);
+            }
+            index += 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::alloc::{
+        string::{String, ToString},
+        vec,
+    };
+
+    #[test]
+    fn serialize_iterator() {
+        let entries = [("a".to_string(), 1), ("b".to_string(), 2)];
+        let iter = entries.iter().map(|(k, v)| (k, v));
+        let mut json = serde_json::Serializer::new(vec![]);
+        serialize_iter::<String, u8, Linear, _, _>(iter, &mut json).unwrap();
+        assert_eq!(json.into_inner(), br#"{"a":1,"b":2}"#);
+
+        let iter = entries.iter().filter(|(_, v)| *v > 1).map(|(k, v)| (k, v));
+        let wrapper = SerializeIter::<String, u8, _>::new(iter).length_hint(1);
+        assert_eq!(serde_json::to_string(&wrapper).unwrap(), r#"{"b":2}"#);
+        let err = serde_json::to_string(&wrapper).unwrap_err().to_string();
+        assert_eq!(err, "the iterator is already consumed");
+    }
+
+    struct Prefix(&'static str);
+
+    impl crate::BaseStrategy<String> for Prefix {
+        type Des = String;
+    }
+
+    impl SerStrategy<String> for Prefix {
+        type SerRet<'s> = String;
+        fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+            d.clone()
+        }
+        fn serialize_with<E: Error>(&self, d: &Self::Des) -> Result<String, E> {
+            Ok([self.0, d].concat())
+        }
+    }
+
+    #[test]
+    fn iterator_strategy() {
+        let entries = [("a".to_string(), 1)];
+        let iter = entries.iter().map(|(k, v)| (k, v));
+        let wrapper = SerializeIter::<String, u8, _, _>::with_strategy(iter, Prefix("p_"));
+        assert_eq!(serde_json::to_string(&wrapper).unwrap(), r#"{"p_a":1}"#);
+    }
+
+    #[test]
+    fn for_each_entry() {
+        let mut sum = 0;
+        let seed = ForEachEntry::<String, u8, _>::new(|_, v| sum += v);
+        let mut de = serde_json::Deserializer::from_str(r#"{"a":1,"b":2}"#);
+        seed.deserialize(&mut de).unwrap();
+        assert_eq!(sum, 3);
+
+        let mut sum = 0;
+        let seed = ForEachEntry::<String, u8, _>::new(|_, v| sum += v);
+        let mut de = serde_json::Deserializer::from_str(r#"[["a",1],["b",2]]"#);
+        seed.deserialize(&mut de).unwrap();
+        assert_eq!(sum, 3);
+
+        let seed = ForEachEntry::<String, u8, _>::new(|_, _| unreachable!());
+        let mut de = serde_json::Deserializer::from_str("null");
+        seed.deserialize(&mut de).unwrap();
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{BaseStrategy, SerdeMap};
+use ::alloc::string::String;
+use serde::{
+    de::{Deserialize, DeserializeOwned},
+    Serialize,
+};
+use serde_json::{Map, Value};
+
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> SerdeMap<K, V, KS, VS> {
+    #[inline]
+    pub fn to_json_string(&self) -> serde_json::Result<String>
+    where
+        Self: Serialize,
+    {
+        serde_json::to_string(self)
+    }
+
+    #[inline]
+    pub fn to_json_pretty(&self) -> serde_json::Result<String>
+    where
+        Self: Serialize,
+    {
+        serde_json::to_string_pretty(self)
+    }
+
+    #[inline]
+    pub fn from_json_str<'de>(s: &'de str) -> serde_json::Result<Self>
+    where
+        Self: Deserialize<'de>,
+    {
+        serde_json::from_str(s)
+    }
+}
+
+impl From<Map<String, Value>> for SerdeMap<String, Value> {
+    #[inline]
+    fn from(map: Map<String, Value>) -> Self {
+        Self::from_iter(map)
+    }
+}
+
+/// Repeated keys are merged, the last value wins
+impl From<SerdeMap<String, Value>> for Map<String, Value> {
+    #[inline]
+    fn from(map: SerdeMap<String, Value>) -> Self {
+        map.0.into_iter().collect()
+    }
+}
+
+/// Deserializes the `Value` as a map, applying the strategies
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> TryFrom<Value> for SerdeMap<K, V, KS, VS>
+where
+    Self: DeserializeOwned,
+{
+    type Error = serde_json::Error;
+
+    #[inline]
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        serde_json::from_value(value)
+    }
+}
+
+/// Serializes into `Value::Object`, applying the strategies
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> TryFrom<SerdeMap<K, V, KS, VS>> for Value
+where
+    SerdeMap<K, V, KS, VS>: Serialize,
+{
+    type Error = serde_json::Error;
+
+    #[inline]
+    fn try_from(map: SerdeMap<K, V, KS, VS>) -> Result<Self, Self::Error> {
+        serde_json::to_value(map)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::alloc::vec;
+    use serde_json::json;
+
+    #[test]
+    fn value_conversions() {
+        let Value::Object(object) = json!({"a": 1, "b": [2]}) else {
+            unreachable!()
+        };
+        let map = SerdeMap::from(object.clone());
+        assert_eq!(
+            map.0,
+            vec![("a".into(), json!(1)), ("b".into(), json!([2]))]
+        );
+        assert_eq!(Map::from(map), object);
+
+        let map: SerdeMap<String, u8, crate::IntKey<i64>> =
+            json!({"1": 2, "3": 4}).try_into().unwrap();
+        assert_eq!(map.0, vec![(1, 2), (3, 4)]);
+        assert_eq!(Value::try_from(map).unwrap(), json!({"1": 2, "3": 4}));
+        assert!(SerdeMap::<String, u8>::try_from(json!([1])).is_err());
+    }
+
+    #[test]
+    fn json_helpers() {
+        let json = r#"{"a":1,"b":2}"#;
+        let map = SerdeMap::<String, u8>::from_json_str(json).unwrap();
+        assert_eq!(map.to_json_string().unwrap(), json);
+        assert_eq!(
+            map.to_json_pretty().unwrap(),
+            "{\n  \"a\": 1,\n  \"b\": 2\n}"
+        );
+    }
+}
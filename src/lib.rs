@@ -1,42 +1,195 @@
-use ::core::{fmt, marker::PhantomData};
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use ::alloc::{vec, vec::Vec};
+use ::core::{
+    borrow::Borrow,
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+#[cfg(feature = "std")]
 use ::std::collections::HashMap;
 use serde::{
-    de::{Deserialize, Deserializer, Error, MapAccess, Visitor},
-    ser::{Serialize, SerializeMap, Serializer},
+    de::{Deserialize, Deserializer, Error, MapAccess, SeqAccess, Visitor},
+    ser::{self, Serialize, Serializer},
 };
 
+mod access;
+mod borrowed;
+mod canonical;
+pub mod csv;
+mod dedup;
+#[cfg(test)]
+mod fixtures;
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "std")]
+mod indexed;
+mod iter;
+#[cfg(feature = "json")]
+mod json;
+mod lossy;
+mod macros;
+mod multi;
+mod pairs;
 #[cfg(feature = "scylla")]
 mod scylla;
-#[cfg(feature = "typesense")]
+mod seed;
+mod set;
+#[cfg(feature = "smallvec")]
+mod small;
+mod sorted;
+mod strategies;
+#[cfg(test)]
+mod tokens;
+#[cfg(feature = "typesense-raw")]
 mod typesense;
+#[cfg(feature = "urlencoded")]
+mod urlencoded;
+pub mod with;
+
+#[cfg(feature = "scylla")]
+pub use self::scylla::{CqlDeStrategy, CqlParseError, CqlSerStrategy, LazySerdeMap, Udt};
+pub use borrowed::SerdeMapRef;
+pub use canonical::Canonical;
+pub use dedup::{
+    AllowDuplicates, DedupSerdeMap, DenyDuplicates, DuplicateKeys, KeepFirst, KeepLast,
+};
+#[cfg(feature = "std")]
+pub use indexed::IndexedSerdeMap;
+pub use iter::{serialize_iter, ForEachEntry, SerializeIter};
+pub use lossy::LossySerdeMap;
+pub use multi::{FlatEntries, GroupedEntries, SerdeMultiMap};
+pub use pairs::AsPairs;
+pub use seed::SerdeMapSeed;
+#[cfg(feature = "derive")]
+pub use serde_map_derive::SerdeMapStrategy;
+pub use set::SerdeSet;
+#[cfg(feature = "smallvec")]
+pub use small::SmallSerdeMap;
+pub use sorted::SortedSerdeMap;
+#[cfg(feature = "http")]
+pub use strategies::HeaderKey;
+#[cfg(feature = "uuid")]
+pub use strategies::UuidKey;
+pub use strategies::{
+    AddrKey, AsIs, ByteBuf, BytesKey, CamelCase, CaseKey, Chain, ChainRet, Convert, DefaultUnknown,
+    DenyUnknown, DisplayFromStr, EnumKey, FromString, Hex, HexCase, HexLower, HexUpper, IntKey,
+    KeyCase, Lowercase, MapWith, MapWithRet, MaxLen, RawBytes, Rule, SnakeCase, Trim, UnknownKey,
+    Validated,
+};
+#[cfg(feature = "base64")]
+pub use strategies::{
+    Base64Engine, Base64Key, Base64Standard, Base64StandardNoPad, Base64UrlSafe, Base64UrlSafeNoPad,
+};
+#[cfg(feature = "intern")]
+pub use strategies::{Interned, Interner};
+#[cfg(feature = "chrono")]
+pub use strategies::{Rfc3339Key, UnixMillisKey};
+#[cfg(feature = "typesense-raw")]
+pub use typesense::{
+    FieldPrefix, Flattened, MapArray, NestedFields, PrefixedFields, TypesenseStrategy,
+    TypesenseType,
+};
+
+/// Used by the generated code, not public API
+#[cfg(any(feature = "derive", feature = "scylla"))]
+#[doc(hidden)]
+pub mod __private {
+    pub use ::alloc::string::{String, ToString};
+    #[cfg(feature = "scylla")]
+    pub use scylla::{
+        deserialize::{value::DeserializeValue, DeserializationError},
+        serialize::value::SerializeValue,
+    };
+    pub use serde::de;
+}
 
 /// Helps to process data at the serialization/deserialization stage, before saving to the inner `Vec`.
+/// Combines [`SerStrategy`] and [`DeStrategy`] (implemented automatically),
+/// a strategy used in one direction only can implement just one of them.
+/// Both share the stored type from [`BaseStrategy`].
 /// Example:
 /// ```rust
 /// use serde::de::Error;
-/// use serde_map::{SerdeMap, SerdeMapStrategy};
+/// use serde_map::{BaseStrategy, DeStrategy, Linear, SerStrategy, SerdeMap};
 ///
+/// #[derive(Default)]
 /// struct StringStrategy;
 ///
-/// impl SerdeMapStrategy<String> for StringStrategy {
+/// impl BaseStrategy<String> for StringStrategy {
 ///     type Des = i64; // deserialized type
+/// }
+///
+/// impl SerStrategy<String> for StringStrategy {
 ///     type SerRet<'s> = String; // serialization return type
 ///
 ///     fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
 ///         d.to_string()
 ///     }
+/// }
 ///
+/// impl DeStrategy<String> for StringStrategy {
 ///     fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
 ///         s.parse().map_err(Error::custom)
 ///     }
+///
+///     // optional, reports the raw key along with its position
+///     fn deserialize_at<E: Error>(&self, index: usize, s: String) -> Result<Self::Des, E> {
+///         s.parse()
+///             .map_err(|e| E::custom(format_args!("entry {index} ({s:?}): {e}")))
+///     }
 /// }
 ///
 /// type SerdeMapString<V> = SerdeMap<String, V, StringStrategy>; // note that `K` here is `String`
 /// // but the inner `Vec` will contain only `i64`
+///
+/// // the same strategy can be applied to values
+/// type SerdeMapStringValues<K> = SerdeMap<K, String, Linear, StringStrategy>;
 /// ```
-pub trait SerdeMapStrategy<Ser>: Sized {
+///
+/// This particular strategy is built in for any `Display` + `FromStr` type:
+/// `SerdeMap<String, V, DisplayFromStr<i64>>`, see [`DisplayFromStr`].
+///
+/// `Ser` can borrow from the input, so no `String` is allocated per key
+/// (formats must support borrowing, and e.g. escaped JSON strings can't be borrowed):
+/// ```rust
+/// use serde::de::Error;
+/// use serde_map::{BaseStrategy, DeStrategy, SerdeMap};
+///
+/// #[derive(Default)]
+/// struct ParseStrategy;
+///
+/// impl BaseStrategy<&str> for ParseStrategy {
+///     type Des = i64;
+/// }
+///
+/// // read-only, so `SerStrategy` is not needed
+/// impl<'a> DeStrategy<&'a str> for ParseStrategy {
+///     fn deserialize<E: Error>(s: &'a str) -> Result<Self::Des, E> {
+///         s.parse().map_err(Error::custom)
+///     }
+/// }
+///
+/// let map: SerdeMap<&str, u8, ParseStrategy> = serde_json::from_str(r#"{"1":2}"#).unwrap();
+/// assert_eq!(map.0, vec![(1, 2)]);
+/// ```
+pub trait SerdeMapStrategy<Ser>: SerStrategy<Ser> + DeStrategy<Ser> {}
+
+impl<Ser, T: SerStrategy<Ser> + DeStrategy<Ser>> SerdeMapStrategy<Ser> for T {}
+
+/// Stored (deserialized) type of a strategy, shared by [`SerStrategy`] and [`DeStrategy`]
+pub trait BaseStrategy<Ser> {
     /// deserialized type
     type Des;
+}
+
+/// Serialization half of [`SerdeMapStrategy`], required by the `Serialize` impls.
+pub trait SerStrategy<Ser>: BaseStrategy<Ser> + Sized {
     /// serialization return type
     type SerRet<'s>: Serialize
     where
@@ -44,15 +197,110 @@ pub trait SerdeMapStrategy<Ser>: Sized {
 
     fn serialize(d: &Self::Des) -> Self::SerRet<'_>;
 
+    /// Fallible version of `serialize`, which is called by the `Serialize` impls.
+    /// Override it if `d` can fail to be represented in the serialized form.
+    #[inline(always)]
+    fn try_serialize<E: ser::Error>(d: &Self::Des) -> Result<Self::SerRet<'_>, E> {
+        Ok(Self::serialize(d))
+    }
+
+    /// Instance version of `try_serialize`, which is called by the [`SerdeMap`] `Serialize` impl
+    /// (via `serialize_format`).
+    /// Override it (and `deserialize_with`) for strategies that carry runtime state,
+    /// then the associated functions are only a stateless fallback.
+    /// The map types which don't store the strategy call the hooks on `KS::default()`.
+    #[inline(always)]
+    fn serialize_with<'s, E: ser::Error>(&self, d: &'s Self::Des) -> Result<Self::SerRet<'s>, E> {
+        Self::try_serialize(d)
+    }
+
+    /// Called by the [`SerdeMap`] `Serialize` impl with the serializer's `is_human_readable()`,
+    /// override it to use a different representation for binary formats.
+    #[inline(always)]
+    fn serialize_format<'s, E: ser::Error>(
+        &self,
+        d: &'s Self::Des,
+        _human_readable: bool,
+    ) -> Result<Self::SerRet<'s>, E> {
+        self.serialize_with(d)
+    }
+}
+
+/// Deserialization half of [`SerdeMapStrategy`], required by the `Deserialize` impls.
+pub trait DeStrategy<Ser>: BaseStrategy<Ser> + Sized {
     fn deserialize<E: Error>(s: Ser) -> Result<Self::Des, E>;
+
+    /// Instance version of `deserialize`, which is called by the [`SerdeMap`] `Deserialize` impl.
+    #[inline(always)]
+    fn deserialize_with<E: Error>(&self, s: Ser) -> Result<Self::Des, E> {
+        Self::deserialize(s)
+    }
+
+    /// Called by the [`SerdeMap`] `Deserialize` impl with the position of the entry in the input.
+    /// The default prefixes the error with the entry index,
+    /// override it to also report the raw serialized value.
+    #[inline(always)]
+    fn deserialize_at<E: Error>(&self, index: usize, s: Ser) -> Result<Self::Des, E> {
+        self.deserialize_with(s).map_err(|e| entry_error(index, e))
+    }
+
+    /// Called by the [`SerdeMap`] `Deserialize` impl (via `deserialize_format`) instead of `deserialize_at`,
+    /// return `Ok(None)` to skip the entry (e.g. unknown or legacy keys).
+    #[inline(always)]
+    fn deserialize_filter<E: Error>(&self, index: usize, s: Ser) -> Result<Option<Self::Des>, E> {
+        self.deserialize_at(index, s).map(Some)
+    }
+
+    /// Called by the [`SerdeMap`] `Deserialize` impl with the deserializer's `is_human_readable()`,
+    /// defaults to `deserialize_filter`.
+    #[inline(always)]
+    fn deserialize_format<E: Error>(
+        &self,
+        index: usize,
+        s: Ser,
+        _human_readable: bool,
+    ) -> Result<Option<Self::Des>, E> {
+        self.deserialize_filter(index, s)
+    }
+}
+
+/// Capacity from `MapAccess::size_hint`/`SeqAccess::size_hint`,
+/// capped at 1 MiB of elements, so malicious hints can't cause a huge allocation
+#[inline]
+pub(crate) fn cautious_capacity<T>(hint: Option<usize>) -> usize {
+    const MAX_PREALLOC_BYTES: usize = 1024 * 1024;
+    let max = MAX_PREALLOC_BYTES / ::core::mem::size_of::<T>().max(1);
+    hint.map_or(0, |hint| hint.min(max))
+}
+
+/// Removes the entries with the matching key in place, returning their values in order
+#[inline]
+pub(crate) fn remove_all<K, V, Q>(entries: &mut Vec<(K, V)>, k: &Q) -> Vec<V>
+where
+    K: Borrow<Q>,
+    Q: PartialEq + ?Sized,
+{
+    entries
+        .extract_if(.., |(key, _)| (*key).borrow() == k)
+        .map(|(_, v)| v)
+        .collect()
+}
+
+/// Prefixes a deserialization error with the index of the entry which caused it
+#[inline]
+pub(crate) fn entry_error<E: Error>(index: usize, e: E) -> E {
+    E::custom(format_args!("entry {index}: {e}"))
 }
 
 /// Linear (one-to-one) serialization strategy
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct Linear;
 
-impl<Ser: Serialize> SerdeMapStrategy<Ser> for Linear {
+impl<Ser> BaseStrategy<Ser> for Linear {
     type Des = Ser;
+}
+
+impl<Ser: Serialize> SerStrategy<Ser> for Linear {
     type SerRet<'s>
         = &'s Ser
     where
@@ -62,32 +310,103 @@ impl<Ser: Serialize> SerdeMapStrategy<Ser> for Linear {
     fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
         d
     }
+}
 
+impl<Ser> DeStrategy<Ser> for Linear {
     #[inline(always)]
     fn deserialize<E>(s: Ser) -> Result<Self::Des, E> {
         Ok(s)
     }
 }
 
+/// Strategy instances are stored inside, so they can carry state
+/// (stateless strategies are zero-sized).
 #[derive(Debug, Clone)]
-pub struct SerdeMap<K, V, KS: SerdeMapStrategy<K> = Linear>(pub Vec<(KS::Des, V)>, PhantomData<KS>);
+pub struct SerdeMap<K, V, KS: BaseStrategy<K> = Linear, VS: BaseStrategy<V> = Linear>(
+    pub Vec<(KS::Des, VS::Des)>,
+    (KS, VS),
+);
 
-impl<K, V, KS: SerdeMapStrategy<K>> SerdeMap<K, V, KS> {
+impl<K, V, KS, VS> SerdeMap<K, V, KS, VS>
+where
+    KS: BaseStrategy<K> + Default,
+    VS: BaseStrategy<V> + Default,
+{
     #[inline]
     pub fn new() -> Self {
-        Self(Vec::new(), PhantomData)
+        Self::with_strategies(KS::default(), VS::default())
     }
 
     #[inline]
     pub fn with_capacity(capacity: usize) -> Self {
-        Self(Vec::with_capacity(capacity), PhantomData)
+        Self(Vec::with_capacity(capacity), Default::default())
     }
+}
 
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> SerdeMap<K, V, KS, VS> {
     #[inline]
-    pub fn insert_unchecked(&mut self, k: KS::Des, v: V) {
+    pub fn with_strategies(key_strategy: KS, value_strategy: VS) -> Self {
+        Self(Vec::new(), (key_strategy, value_strategy))
+    }
+
+    #[inline(always)]
+    pub fn key_strategy(&self) -> &KS {
+        &self.1 .0
+    }
+
+    #[inline(always)]
+    pub fn value_strategy(&self) -> &VS {
+        &self.1 .1
+    }
+
+    #[inline]
+    pub fn insert_unchecked(&mut self, k: KS::Des, v: VS::Des) {
+        self.0.push((k, v));
+    }
+
+    /// If the last entry has the same key, merges `v` into its value using `merge`,
+    /// otherwise pushes a new entry.
+    #[inline]
+    pub fn push_or_merge_last<F>(&mut self, k: KS::Des, v: VS::Des, merge: F)
+    where
+        KS::Des: PartialEq,
+        F: FnOnce(&mut VS::Des, VS::Des),
+    {
+        self.push_or_merge_last_by(k, v, PartialEq::eq, merge)
+    }
+
+    /// Same as [`SerdeMap::push_or_merge_last`], but keys are compared with `eq`.
+    #[inline]
+    pub fn push_or_merge_last_by<E, F>(&mut self, k: KS::Des, v: VS::Des, eq: E, merge: F)
+    where
+        E: FnOnce(&KS::Des, &KS::Des) -> bool,
+        F: FnOnce(&mut VS::Des, VS::Des),
+    {
+        if let Some(last) = self.0.last_mut() {
+            if eq(&last.0, &k) {
+                merge(&mut last.1, v);
+                return;
+            }
+        }
+
         self.0.push((k, v));
     }
 
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[(KS::Des, VS::Des)] {
+        &self.0
+    }
+
+    #[inline(always)]
+    pub fn as_mut_slice(&mut self) -> &mut [(KS::Des, VS::Des)] {
+        &mut self.0
+    }
+
+    #[inline(always)]
+    pub fn as_map_ref(&self) -> SerdeMapRef<'_, K, V, KS, VS> {
+        self.into()
+    }
+
     #[inline(always)]
     pub fn len(&self) -> usize {
         self.0.len()
@@ -97,9 +416,132 @@ impl<K, V, KS: SerdeMapStrategy<K>> SerdeMap<K, V, KS> {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Returns a reference to the value of the first entry with the matching key.
+    ///
+    /// Lookup is a linear scan. Note that it shadows the slice's `get` by index.
+    #[inline]
+    pub fn get<Q>(&self, k: &Q) -> Option<&VS::Des>
+    where
+        KS::Des: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.position(k).map(|i| &self.0[i].1)
+    }
+
+    /// Returns a mutable reference to the value of the first entry with the matching key.
+    #[inline]
+    pub fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut VS::Des>
+    where
+        KS::Des: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.position(k).map(|i| &mut self.0[i].1)
+    }
+
+    #[inline]
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        KS::Des: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.position(k).is_some()
+    }
+
+    /// Removes the first entry with the matching key, shifting the following entries
+    /// to preserve the order.
+    #[inline]
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<VS::Des>
+    where
+        KS::Des: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.position(k).map(|i| self.0.remove(i).1)
+    }
+
+    /// Returns an iterator over the values of all entries with the matching key.
+    #[inline]
+    pub fn get_all<'a, Q>(&'a self, k: &'a Q) -> impl Iterator<Item = &'a VS::Des>
+    where
+        KS::Des: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.0
+            .iter()
+            .filter(move |(key, _)| key.borrow() == k)
+            .map(|(_, v)| v)
+    }
+
+    /// Removes all entries with the matching key, returning their values in order.
+    #[inline]
+    pub fn remove_all<Q>(&mut self, k: &Q) -> Vec<VS::Des>
+    where
+        KS::Des: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        remove_all(&mut self.0, k)
+    }
+
+    /// Returns the value of the first entry with the matching key,
+    /// inserting `f()` at the end if there is none.
+    #[inline]
+    pub fn get_or_insert_with<F: FnOnce() -> VS::Des>(&mut self, k: KS::Des, f: F) -> &mut VS::Des
+    where
+        KS::Des: PartialEq,
+    {
+        let i = match self.position(&k) {
+            Some(i) => i,
+            None => {
+                self.0.push((k, f()));
+                self.0.len() - 1
+            }
+        };
+        &mut self.0[i].1
+    }
+
+    #[inline]
+    fn position<Q>(&self, k: &Q) -> Option<usize>
+    where
+        KS::Des: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.0.iter().position(|(key, _)| key.borrow() == k)
+    }
+
+    /// Consumes the map, swapping keys and values (and their strategies), preserving the order.
+    #[inline]
+    pub fn invert(self) -> SerdeMap<V, K, VS, KS> {
+        let (ks, vs) = self.1;
+        SerdeMap(self.0.into_iter().map(|(k, v)| (v, k)).collect(), (vs, ks))
+    }
+
+    /// Same as [`SerdeMap::invert`], but with a custom strategy for the new keys.
+    #[inline]
+    pub fn invert_with<NK, NKS: BaseStrategy<NK, Des = VS::Des>>(
+        self,
+        key_strategy: NKS,
+    ) -> SerdeMap<NK, K, NKS, KS> {
+        let (ks, _) = self.1;
+        SerdeMap(
+            self.0.into_iter().map(|(k, v)| (v, k)).collect(),
+            (key_strategy, ks),
+        )
+    }
+
+    /// Consumes the map, returning an iterator over the keys in the original order.
+    #[inline]
+    pub fn into_keys(self) -> impl Iterator<Item = KS::Des> {
+        self.0.into_iter().map(|(k, _)| k)
+    }
+
+    /// Consumes the map, returning an iterator over the values in the original order.
+    #[inline]
+    pub fn into_values(self) -> impl Iterator<Item = VS::Des> {
+        self.0.into_iter().map(|(_, v)| v)
+    }
 }
 
-impl<K, V, KS: SerdeMapStrategy<K>> SerdeMap<K, Vec<V>, KS> {
+impl<K, V, W, KS: BaseStrategy<K>, VS: BaseStrategy<W, Des = Vec<V>>> SerdeMap<K, W, KS, VS> {
     #[inline]
     pub fn push_to_same_last(&mut self, k: KS::Des, v: V)
     where
@@ -114,18 +556,52 @@ impl<K, V, KS: SerdeMapStrategy<K>> SerdeMap<K, Vec<V>, KS> {
 
         self.0.push((k, vec![v]));
     }
+
+    /// Groups consecutive equal keys of the iterator into `Vec` values,
+    /// so the input should be sorted (or at least clustered) by key.
+    #[inline]
+    pub fn from_grouped_iter<I: IntoIterator<Item = (KS::Des, V)>>(iter: I) -> Self
+    where
+        KS::Des: PartialEq,
+        KS: Default,
+        VS: Default,
+    {
+        let mut map = Self::new();
+        for (k, v) in iter {
+            map.push_to_same_last(k, v);
+        }
+        map
+    }
 }
 
-impl<K, V, KS: SerdeMapStrategy<K>> Default for SerdeMap<K, V, KS> {
+impl<K, V, KS: BaseStrategy<K> + Default, VS: BaseStrategy<V> + Default> Default
+    for SerdeMap<K, V, KS, VS>
+{
     #[inline]
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<K, V, KS: SerdeMapStrategy<K>> IntoIterator for SerdeMap<K, V, KS> {
-    type Item = (KS::Des, V);
-    type IntoIter = std::vec::IntoIter<Self::Item>;
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> Deref for SerdeMap<K, V, KS, VS> {
+    type Target = [(KS::Des, VS::Des)];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> DerefMut for SerdeMap<K, V, KS, VS> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> IntoIterator for SerdeMap<K, V, KS, VS> {
+    type Item = (KS::Des, VS::Des);
+    type IntoIter = ::alloc::vec::IntoIter<Self::Item>;
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
@@ -133,9 +609,11 @@ impl<K, V, KS: SerdeMapStrategy<K>> IntoIterator for SerdeMap<K, V, KS> {
     }
 }
 
-impl<'a, K, V, KS: SerdeMapStrategy<K>> IntoIterator for &'a SerdeMap<K, V, KS> {
-    type Item = &'a (KS::Des, V);
-    type IntoIter = std::slice::Iter<'a, (KS::Des, V)>;
+impl<'a, K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> IntoIterator
+    for &'a SerdeMap<K, V, KS, VS>
+{
+    type Item = &'a (KS::Des, VS::Des);
+    type IntoIter = ::core::slice::Iter<'a, (KS::Des, VS::Des)>;
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
@@ -143,9 +621,11 @@ impl<'a, K, V, KS: SerdeMapStrategy<K>> IntoIterator for &'a SerdeMap<K, V, KS>
     }
 }
 
-impl<'a, K, V, KS: SerdeMapStrategy<K>> IntoIterator for &'a mut SerdeMap<K, V, KS> {
-    type Item = &'a mut (KS::Des, V);
-    type IntoIter = std::slice::IterMut<'a, (KS::Des, V)>;
+impl<'a, K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> IntoIterator
+    for &'a mut SerdeMap<K, V, KS, VS>
+{
+    type Item = &'a mut (KS::Des, VS::Des);
+    type IntoIter = ::core::slice::IterMut<'a, (KS::Des, VS::Des)>;
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
@@ -153,50 +633,69 @@ impl<'a, K, V, KS: SerdeMapStrategy<K>> IntoIterator for &'a mut SerdeMap<K, V,
     }
 }
 
-impl<K, V, KS: SerdeMapStrategy<K>> FromIterator<(KS::Des, V)> for SerdeMap<K, V, KS> {
+impl<K, V, KS: BaseStrategy<K> + Default, VS: BaseStrategy<V> + Default>
+    FromIterator<(KS::Des, VS::Des)> for SerdeMap<K, V, KS, VS>
+{
     #[inline]
-    fn from_iter<T: IntoIterator<Item = (KS::Des, V)>>(iter: T) -> Self {
-        Self(iter.into_iter().collect(), PhantomData)
+    fn from_iter<T: IntoIterator<Item = (KS::Des, VS::Des)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect(), Default::default())
     }
 }
 
-impl<K, V, KS: SerdeMapStrategy<K>> From<Vec<(KS::Des, V)>> for SerdeMap<K, V, KS> {
+impl<K, V, KS: BaseStrategy<K> + Default, VS: BaseStrategy<V> + Default>
+    From<Vec<(KS::Des, VS::Des)>> for SerdeMap<K, V, KS, VS>
+{
     #[inline]
-    fn from(data: Vec<(KS::Des, V)>) -> Self {
-        Self(data, PhantomData)
+    fn from(data: Vec<(KS::Des, VS::Des)>) -> Self {
+        Self(data, Default::default())
     }
 }
 
-impl<K, V, KS: SerdeMapStrategy<K>, S> From<HashMap<KS::Des, V, S>> for SerdeMap<K, V, KS> {
+#[cfg(feature = "std")]
+impl<K, V, KS: BaseStrategy<K> + Default, VS: BaseStrategy<V> + Default, S>
+    From<HashMap<KS::Des, VS::Des, S>> for SerdeMap<K, V, KS, VS>
+{
     #[inline]
-    fn from(hash: HashMap<KS::Des, V, S>) -> Self {
+    fn from(hash: HashMap<KS::Des, VS::Des, S>) -> Self {
         let data = hash.into_iter().collect();
-        Self(data, PhantomData)
+        Self(data, Default::default())
     }
 }
 
-impl<K, V, KS: SerdeMapStrategy<K>, S> From<SerdeMap<K, V, KS>> for HashMap<KS::Des, V, S>
+#[cfg(feature = "std")]
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>, S> From<SerdeMap<K, V, KS, VS>>
+    for HashMap<KS::Des, VS::Des, S>
 where
-    <KS as SerdeMapStrategy<K>>::Des: std::cmp::Eq + std::hash::Hash,
-    S: Default + std::hash::BuildHasher,
+    KS::Des: ::core::cmp::Eq + ::core::hash::Hash,
+    S: Default + ::core::hash::BuildHasher,
 {
     #[inline]
-    fn from(v: SerdeMap<K, V, KS>) -> Self {
+    fn from(v: SerdeMap<K, V, KS, VS>) -> Self {
         v.0.into_iter().collect()
     }
 }
 
-impl<K: Serialize, V: Serialize, KS: SerdeMapStrategy<K>> Serialize for SerdeMap<K, V, KS> {
+impl<K, V, KS: SerStrategy<K>, VS: SerStrategy<V>> Serialize for SerdeMap<K, V, KS, VS> {
     #[inline]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut map = serializer.serialize_map(Some(self.len()))?;
-        for (k, v) in self {
-            map.serialize_entry(&KS::serialize(k), v)?;
-        }
-        map.end()
+        self.as_map_ref().serialize(serializer)
+    }
+}
+
+/// Self-describing (human-readable) formats may contain a sequence of pairs instead of a map
+#[inline]
+fn deserialize_map_or_seq<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+where
+    D: Deserializer<'de>,
+    V: Visitor<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(visitor)
+    } else {
+        deserializer.deserialize_map(visitor)
     }
 }
 
@@ -207,6 +706,8 @@ macro_rules! map_impl {
         $ty:ident <K $(: $kbound1:ident $(+ $kbound2:ident)*)*, V $(, $typaram:ident : $bound1:ident $(<$bound1_1:ident>)? $(+ $bound2:ident)*)*>, // added `$(<$bound1_1:ident>)?`
         $access:ident,
         $with_capacity:expr,
+        |$values:ident, $index:ident, $key:ident, $value:ident, $human_readable:ident| $insert:expr, // added
+        in_place: $clear:expr, // added
     ) => {
         $(#[$attr])*
         impl<'de, K, V $(, $typaram)*> Deserialize<'de> for $ty<K, V $(, $typaram)*>
@@ -221,6 +722,7 @@ macro_rules! map_impl {
             {
                 struct MapVisitor<K, V $(, $typaram: $bound1<$($bound1_1)?> $(+ $bound2)*)*> { // added `: $bound1 $(+ $bound2)*`
                     marker: PhantomData<$ty<K, V $(, $typaram)*>>,
+                    human_readable: bool, // added
                 }
 
                 impl<'de, K, V $(, $typaram)*> Visitor<'de> for MapVisitor<K, V $(, $typaram)*>
@@ -232,7 +734,7 @@ macro_rules! map_impl {
                     type Value = $ty<K, V $(, $typaram)*>;
 
                     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                        formatter.write_str("a map")
+                        formatter.write_str("a map or a sequence of pairs")
                     }
 
                     #[inline]
@@ -240,49 +742,502 @@ macro_rules! map_impl {
                     where
                         A: MapAccess<'de>,
                     {
-                        let mut values = $with_capacity;
+                        let mut $values = $with_capacity;
+                        let $human_readable = self.human_readable;
 
-                        while let Some((key, value)) = $access.next_entry()? {
-                            values.insert_unchecked(KS::deserialize(key)?, value);
+                        let mut $index = 0;
+                        while let Some(($key, $value)) = $access.next_entry()? {
+                            $insert;
+                            $index += 1;
                         }
 
-                        Ok(values)
+                        Ok($values)
+                    }
+
+                    // added
+                    #[inline]
+                    fn visit_seq<A>(self, mut $access: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: SeqAccess<'de>,
+                    {
+                        let mut $values = $with_capacity;
+                        let $human_readable = self.human_readable;
+
+                        let mut $index = 0;
+                        while let Some(($key, $value)) = $access.next_element()? {
+                            $insert;
+                            $index += 1;
+                        }
+
+                        Ok($values)
+                    }
+
+                    // added
+                    #[inline]
+                    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                        Ok(Default::default())
+                    }
+
+                    #[inline]
+                    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                        self.visit_unit()
+                    }
+
+                    #[inline]
+                    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+                    where
+                        D: Deserializer<'de>,
+                    {
+                        let human_readable = deserializer.is_human_readable();
+                        deserialize_map_or_seq(deserializer, MapVisitor { human_readable, ..self })
                     }
                 }
 
-                let visitor = MapVisitor { marker: PhantomData };
-                deserializer.deserialize_map(visitor)
+                let visitor = MapVisitor {
+                    marker: PhantomData,
+                    human_readable: deserializer.is_human_readable(),
+                };
+                deserialize_map_or_seq(deserializer, visitor) // added
             }
+
+            // added
+            fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct InPlaceVisitor<'a, K, V $(, $typaram: $bound1<$($bound1_1)?> $(+ $bound2)*)*>(
+                    &'a mut $ty<K, V $(, $typaram)*>,
+                    bool,
+                );
+
+                impl<'de, K, V $(, $typaram)*> Visitor<'de> for InPlaceVisitor<'_, K, V $(, $typaram)*>
+                where
+                    K: Deserialize<'de> $(+ $kbound1 $(+ $kbound2)*)*,
+                    V: Deserialize<'de>,
+                    $($typaram: $bound1<$($bound1_1)?> $(+ $bound2)*),*
+                {
+                    type Value = ();
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("a map or a sequence of pairs")
+                    }
+
+                    #[inline]
+                    fn visit_map<A>(self, mut $access: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: MapAccess<'de>,
+                    {
+                        let $human_readable = self.1;
+                        let $values = self.0;
+                        $clear;
+
+                        let mut $index = 0;
+                        while let Some(($key, $value)) = $access.next_entry()? {
+                            $insert;
+                            $index += 1;
+                        }
+
+                        Ok(())
+                    }
+
+                    #[inline]
+                    fn visit_seq<A>(self, mut $access: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: SeqAccess<'de>,
+                    {
+                        let $human_readable = self.1;
+                        let $values = self.0;
+                        $clear;
+
+                        let mut $index = 0;
+                        while let Some(($key, $value)) = $access.next_element()? {
+                            $insert;
+                            $index += 1;
+                        }
+
+                        Ok(())
+                    }
+
+                    #[inline]
+                    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                        self.0 .0.clear();
+                        Ok(())
+                    }
+
+                    #[inline]
+                    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                        self.visit_unit()
+                    }
+
+                    #[inline]
+                    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+                    where
+                        D: Deserializer<'de>,
+                    {
+                        let human_readable = deserializer.is_human_readable();
+                        deserialize_map_or_seq(deserializer, InPlaceVisitor(self.0, human_readable))
+                    }
+                }
+
+                let human_readable = deserializer.is_human_readable();
+                deserialize_map_or_seq(deserializer, InPlaceVisitor(place, human_readable))
+            }
+        }
+    }
+}
+
+map_impl! {
+    SerdeMap<K, V, KS: DeStrategy<K> + Default, VS: DeStrategy<V> + Default>,
+    map,
+    SerdeMap::<K, V, KS, VS>::with_capacity(cautious_capacity::<(KS::Des, VS::Des)>(map.size_hint())),
+    |values, index, key, value, human_readable| {
+        let k = values.1 .0.deserialize_format(index, key, human_readable)?;
+        let v = values.1 .1.deserialize_format(index, value, human_readable)?;
+        if let (Some(k), Some(v)) = (k, v) {
+            values.insert_unchecked(k, v)
         }
+    },
+    in_place: {
+        values.0.clear();
+        values.0.reserve(cautious_capacity::<(KS::Des, VS::Des)>(map.size_hint()))
+    },
+}
+
+/// Entries deserialized without a value strategy, used by the other map types.
+/// They don't store a strategy instance, so the hooks of `KS::default()` are applied.
+pub(crate) struct Entries<K, V, KS: BaseStrategy<K>>(pub(crate) Vec<(KS::Des, V)>, KS);
+
+impl<K, V, KS: BaseStrategy<K> + Default> Default for Entries<K, V, KS> {
+    #[inline]
+    fn default() -> Self {
+        Self(Vec::new(), KS::default())
     }
 }
 
 map_impl! {
-    SerdeMap<K, V, KS: SerdeMapStrategy<K> >,
+    Entries<K, V, KS: DeStrategy<K> + Default>,
     map,
-    SerdeMap::new(),
+    Entries(Vec::with_capacity(cautious_capacity::<(KS::Des, V)>(map.size_hint())), KS::default()),
+    |values, index, key, value, human_readable| {
+        if let Some(k) = values.1.deserialize_format(index, key, human_readable)? {
+            values.0.push((k, value))
+        }
+    },
+    in_place: values.0.clear(),
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use ::alloc::{
+        borrow::ToOwned,
+        string::{String, ToString},
+    };
 
+    #[derive(Default)]
     struct StringStrategy;
 
-    impl SerdeMapStrategy<String> for StringStrategy {
+    impl BaseStrategy<String> for StringStrategy {
         type Des = i64;
+    }
+
+    impl SerStrategy<String> for StringStrategy {
         type SerRet<'s> = String;
         fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
             d.to_string()
         }
+    }
+
+    impl DeStrategy<String> for StringStrategy {
+        fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+            s.parse().map_err(Error::custom)
+        }
+    }
+
+    #[test]
+    fn borrowed_lookup() {
+        let mut map: SerdeMap<String, u8> = vec![("a".to_owned(), 1), ("b".to_owned(), 2)].into();
+        assert_eq!(map.get("b"), Some(&2));
+        assert!(map.contains_key("a"));
+        assert_eq!(map.remove("a"), Some(1));
+        assert!(!map.contains_key("a"));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn merge_last() {
+        let mut map: SerdeMap<u8, u32> = SerdeMap::new();
+        for (k, v) in [(1, 1), (1, 2), (2, 3), (1, 4)] {
+            map.push_or_merge_last(k, v, |acc, v| *acc += v);
+        }
+        assert_eq!(map.0, vec![(1, 3), (2, 3), (1, 4)]);
+    }
+
+    #[test]
+    fn duplicate_keys() {
+        let mut map: SerdeMap<u8, u8> = vec![(1, 1), (2, 2), (1, 3)].into();
+        assert_eq!(map.get_all(&1).copied().collect::<Vec<_>>(), vec![1, 3]);
+        let capacity = map.0.capacity();
+        assert_eq!(map.remove_all(&1), vec![1, 3]);
+        assert_eq!(map.0, vec![(2, 2)]);
+        assert_eq!(map.0.capacity(), capacity);
+    }
+
+    #[test]
+    fn value_strategy() {
+        let json = r#"{"1":"2","3":"4"}"#;
+        let map: SerdeMap<String, String, StringStrategy, StringStrategy> =
+            serde_json::from_str(json).unwrap();
+        assert_eq!(map.0, vec![(1, 2), (3, 4)]);
+        assert_eq!(serde_json::to_string(&map).unwrap(), json);
+    }
+
+    #[test]
+    fn entry_error_index() {
+        let json = r#"{"1":"2","x":"4"}"#;
+        let Err(err) = serde_json::from_str::<SerdeMap<String, String, StringStrategy>>(json)
+        else {
+            panic!("expected an error");
+        };
+        let err = err.to_string();
+        assert!(err.starts_with("entry 1: invalid digit"), "{err}");
+    }
+
+    #[derive(Default)]
+    struct Utf8Strategy;
+
+    impl BaseStrategy<String> for Utf8Strategy {
+        type Des = Vec<u8>;
+    }
+
+    impl SerStrategy<String> for Utf8Strategy {
+        type SerRet<'s> = &'s str;
+        fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+            ::core::str::from_utf8(d).unwrap()
+        }
+        fn try_serialize<E: ser::Error>(d: &Self::Des) -> Result<Self::SerRet<'_>, E> {
+            ::core::str::from_utf8(d).map_err(E::custom)
+        }
+    }
+
+    impl DeStrategy<String> for Utf8Strategy {
         fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+            Ok(s.into_bytes())
+        }
+    }
+
+    #[test]
+    fn fallible_serialize() {
+        let map: SerdeMap<String, u8, Utf8Strategy> = vec![(vec![b'a'], 1)].into();
+        assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"a":1}"#);
+        let map: SerdeMap<String, u8, Utf8Strategy> = vec![(vec![0xff], 1)].into();
+        assert!(serde_json::to_string(&map).is_err());
+    }
+
+    #[derive(Default)]
+    struct PrefixStrategy(String);
+
+    impl BaseStrategy<String> for PrefixStrategy {
+        type Des = String;
+    }
+
+    impl SerStrategy<String> for PrefixStrategy {
+        type SerRet<'s> = String;
+        fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+            d.clone()
+        }
+        fn serialize_with<E: ser::Error>(&self, d: &Self::Des) -> Result<String, E> {
+            Ok([self.0.as_str(), d].concat())
+        }
+    }
+
+    impl DeStrategy<String> for PrefixStrategy {
+        fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+            Ok(s)
+        }
+        fn deserialize_with<E: Error>(&self, s: String) -> Result<Self::Des, E> {
+            s.strip_prefix(self.0.as_str())
+                .map(ToOwned::to_owned)
+                .ok_or_else(|| E::custom("missing prefix"))
+        }
+    }
+
+    #[test]
+    fn stateful_strategy() {
+        let mut map: SerdeMap<String, u8, PrefixStrategy> =
+            SerdeMap::with_strategies(PrefixStrategy("p_".to_owned()), Linear);
+        map.insert_unchecked("a".to_owned(), 1);
+        assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"p_a":1}"#);
+    }
+
+    #[test]
+    fn filter_entries() {
+        let json = r#"{"a":1,"_b":2,"c":3}"#;
+        let map: SerdeMap<String, u8, crate::fixtures::PublicKeys> =
+            serde_json::from_str(json).unwrap();
+        assert_eq!(map.0, vec![("a".to_owned(), 1), ("c".to_owned(), 3)]);
+    }
+
+    #[derive(Default)]
+    struct ParseStrategy;
+
+    impl BaseStrategy<&str> for ParseStrategy {
+        type Des = i64;
+    }
+
+    impl<'a> SerStrategy<&'a str> for ParseStrategy {
+        type SerRet<'s>
+            = String
+        where
+            'a: 's;
+        fn serialize(d: &Self::Des) -> String {
+            d.to_string()
+        }
+    }
+
+    impl<'a> DeStrategy<&'a str> for ParseStrategy {
+        fn deserialize<E: Error>(s: &'a str) -> Result<Self::Des, E> {
             s.parse().map_err(Error::custom)
         }
     }
 
+    #[test]
+    fn borrowed_keys() {
+        let json = r#"{"1":2,"3":4}"#;
+        let map: SerdeMap<&str, u8, ParseStrategy> = serde_json::from_str(json).unwrap();
+        assert_eq!(map.0, vec![(1, 2), (3, 4)]);
+        assert_eq!(serde_json::to_string(&map).unwrap(), json);
+    }
+
+    #[test]
+    fn in_place() {
+        let mut map: SerdeMap<String, u8> = SerdeMap::with_capacity(16);
+        let mut de = serde_json::Deserializer::from_str(r#"{"a":1,"b":2}"#);
+        Deserialize::deserialize_in_place(&mut de, &mut map).unwrap();
+        assert_eq!(map.0, vec![("a".to_owned(), 1), ("b".to_owned(), 2)]);
+        assert!(map.0.capacity() >= 16);
+    }
+
+    #[test]
+    fn capacity_hint() {
+        assert_eq!(cautious_capacity::<u64>(None), 0);
+        assert_eq!(cautious_capacity::<u64>(Some(3)), 3);
+        assert_eq!(cautious_capacity::<u64>(Some(usize::MAX)), 128 * 1024);
+        assert_eq!(cautious_capacity::<()>(Some(usize::MAX)), 1024 * 1024);
+    }
+
+    #[test]
+    fn pairs() {
+        let json = r#"[["1","2"],["3","4"]]"#;
+        let map: SerdeMap<String, String, StringStrategy> = serde_json::from_str(json).unwrap();
+        assert_eq!(map.0, vec![(1, "2".to_owned()), (3, "4".to_owned())]);
+    }
+
+    #[test]
+    fn null_is_empty() {
+        let map: SerdeMap<String, u8> = serde_json::from_str("null").unwrap();
+        assert!(map.is_empty());
+        let sorted: SortedSerdeMap<String, u8> = serde_json::from_str("null").unwrap();
+        assert!(sorted.is_empty());
+    }
+
+    /// Marks keys with the format type
+    #[derive(Default)]
+    struct FormatStrategy;
+
+    impl BaseStrategy<String> for FormatStrategy {
+        type Des = String;
+    }
+
+    impl SerStrategy<String> for FormatStrategy {
+        type SerRet<'s> = String;
+        fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+            d.clone()
+        }
+        fn serialize_format<E: ser::Error>(&self, d: &String, hr: bool) -> Result<String, E> {
+            Ok([d.as_str(), if hr { "+h" } else { "+b" }].concat())
+        }
+    }
+
+    impl DeStrategy<String> for FormatStrategy {
+        fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+            Ok(s)
+        }
+        fn deserialize_format<E: Error>(
+            &self,
+            _: usize,
+            s: String,
+            hr: bool,
+        ) -> Result<Option<String>, E> {
+            let suffix = if hr { "+h" } else { "+b" };
+            Ok(s.strip_suffix(suffix).map(ToOwned::to_owned))
+        }
+    }
+
+    #[test]
+    fn human_readable() {
+        let map: SerdeMap<String, u8, FormatStrategy> =
+            serde_json::from_str(r#"{"a+h":1,"b+b":2}"#).unwrap();
+        assert_eq!(map.0, vec![("a".to_owned(), 1)]);
+        assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"a+h":1}"#);
+    }
+
+    /// Write-only
+    struct HexStrategy;
+
+    impl BaseStrategy<String> for HexStrategy {
+        type Des = u8;
+    }
+
+    impl SerStrategy<String> for HexStrategy {
+        type SerRet<'s> = String;
+        fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+            ::alloc::format!("{d:x}")
+        }
+    }
+
+    /// Deserialize-only
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Id(u8);
+
+    #[test]
+    fn one_direction() {
+        let map: SerdeMap<String, u8, HexStrategy> = SerdeMap::with_strategies(HexStrategy, Linear);
+        let mut map = map;
+        map.insert_unchecked(255, 1);
+        assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"ff":1}"#);
+
+        let map: SerdeMap<String, Id> = serde_json::from_str(r#"{"a":1}"#).unwrap();
+        assert_eq!(map.0, vec![("a".to_owned(), Id(1))]);
+    }
+
+    /// Marker key type, which is not `Serialize`
+    struct Tag;
+
+    struct TagStrategy;
+
+    impl BaseStrategy<Tag> for TagStrategy {
+        type Des = u8;
+    }
+
+    impl SerStrategy<Tag> for TagStrategy {
+        type SerRet<'s> = &'s u8;
+        fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+            d
+        }
+    }
+
+    #[test]
+    fn marker_key() {
+        let mut map: SerdeMap<Tag, u8, TagStrategy> =
+            SerdeMap::with_strategies(TagStrategy, Linear);
+        map.insert_unchecked(1, 2);
+        assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"1":2}"#);
+    }
+
     #[test]
     fn change_strategy() {
         let old: SerdeMap<String, u8, StringStrategy> = Default::default();
-        let _new: SerdeMap<i64, u8, Linear> = SerdeMap(old.0, PhantomData);
+        let _new: SerdeMap<i64, u8, Linear> = SerdeMap(old.0, Default::default());
     }
 }
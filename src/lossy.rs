@@ -0,0 +1,121 @@
+use crate::{
+    access::{deserialize_entries, EntryAccess, VisitEntries},
+    cautious_capacity, BaseStrategy, DeStrategy, Linear, SerdeMap,
+};
+use ::alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use ::core::{fmt, marker::PhantomData};
+use serde::de::{Deserialize, Deserializer};
+
+/// [`SerdeMap`] deserialized in the lossy mode: entries which fail the strategies
+/// are skipped and their errors are collected, instead of failing the whole input.
+/// Errors of the format itself (e.g. malformed input) still fail the deserialization.
+pub struct LossySerdeMap<K, V, KS: BaseStrategy<K> = Linear, VS: BaseStrategy<V> = Linear> {
+    pub map: SerdeMap<K, V, KS, VS>,
+    /// messages of the skipped entries, in the input order
+    pub errors: Vec<String>,
+}
+
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> fmt::Debug for LossySerdeMap<K, V, KS, VS>
+where
+    SerdeMap<K, V, KS, VS>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LossySerdeMap")
+            .field("map", &self.map)
+            .field("errors", &self.errors)
+            .finish()
+    }
+}
+
+impl<'de, K, V, KS, VS> Deserialize<'de> for LossySerdeMap<K, V, KS, VS>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    KS: DeStrategy<K> + Default,
+    VS: DeStrategy<V> + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MapVisitor<K, V, KS, VS> {
+            marker: PhantomData<(K, V, KS, VS)>,
+            human_readable: bool,
+        }
+
+        impl<'de, K, V, KS, VS> VisitEntries<'de> for MapVisitor<K, V, KS, VS>
+        where
+            K: Deserialize<'de>,
+            V: Deserialize<'de>,
+            KS: DeStrategy<K> + Default,
+            VS: DeStrategy<V> + Default,
+        {
+            type Value = LossySerdeMap<K, V, KS, VS>;
+
+            #[inline]
+            fn visit_entries<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+            where
+                A: EntryAccess<'de>,
+            {
+                let capacity = cautious_capacity::<(KS::Des, VS::Des)>(access.size_hint());
+                let mut map = SerdeMap::<K, V, KS, VS>::with_capacity(capacity);
+                let mut errors = Vec::new();
+
+                let mut index = 0;
+                while let Some((key, value)) = access.next_entry()? {
+                    let (ks, vs) = (map.key_strategy(), map.value_strategy());
+                    let entry: Result<_, A::Error> = ks
+                        .deserialize_format(index, key, self.human_readable)
+                        .and_then(|k| {
+                            Ok((k, vs.deserialize_format(index, value, self.human_readable)?))
+                        });
+                    match entry {
+                        Ok((Some(k), Some(v))) => map.insert_unchecked(k, v),
+                        Ok(_) => {}
+                        Err(e) => errors.push(e.to_string()),
+                    }
+                    index += 1;
+                }
+
+                Ok(LossySerdeMap { map, errors })
+            }
+        }
+
+        let visitor = MapVisitor {
+            marker: PhantomData,
+            human_readable: deserializer.is_human_readable(),
+        };
+        deserialize_entries(deserializer, visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IntKey;
+    use ::alloc::vec;
+
+    #[test]
+    fn lossy() {
+        let json = r#"{"1":2,"x":3,"4":5}"#;
+        let lossy: LossySerdeMap<String, u8, IntKey<i64>> = serde_json::from_str(json).unwrap();
+        assert_eq!(lossy.map.0, vec![(1, 2), (4, 5)]);
+        assert_eq!(lossy.errors.len(), 1);
+        assert!(
+            lossy.errors[0].starts_with("entry 1: "),
+            "{}",
+            lossy.errors[0]
+        );
+
+        let json = r#"[["1",2],["x",3]]"#;
+        let lossy: LossySerdeMap<String, u8, IntKey<i64>> = serde_json::from_str(json).unwrap();
+        assert_eq!(lossy.map.0, vec![(1, 2)]);
+        assert_eq!(lossy.errors.len(), 1);
+
+        let lossy: LossySerdeMap<String, u8> = serde_json::from_str("null").unwrap();
+        assert!(lossy.map.is_empty() && lossy.errors.is_empty());
+    }
+}
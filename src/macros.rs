@@ -0,0 +1,68 @@
+/// Creates a [`SerdeMap`](crate::SerdeMap) with the `Linear` strategies,
+/// the entries are kept in the written order (repeated keys too)
+/// ```rust
+/// use serde_map::{serde_map, SerdeMap};
+///
+/// let map: SerdeMap<String, u8> = serde_map! {
+///     "b".into() => 1,
+///     "a".into() => 2,
+/// };
+/// assert_eq!(map.0, [("b".into(), 1), ("a".into(), 2)]);
+/// ```
+#[macro_export]
+macro_rules! serde_map {
+    ($($k:expr => $v:expr),* $(,)?) => {
+        <$crate::SerdeMap<_, _> as ::core::iter::FromIterator<_>>::from_iter([$(($k, $v)),*])
+    };
+}
+
+/// Creates a [`SerdeMap`](crate::SerdeMap) with the given key strategy (and value strategy),
+/// the entries are the stored (deserialized) keys and values, kept in the written order
+/// ```rust
+/// use serde_map::{serde_map_with_strategy, IntKey, Linear, SerdeMap};
+///
+/// let map: SerdeMap<String, &str, IntKey<u8>> =
+///     serde_map_with_strategy!(IntKey::default(); 2 => "a", 1 => "b");
+/// assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"2":"a","1":"b"}"#);
+///
+/// let map = serde_map_with_strategy!(IntKey::<u8>::default(), Linear; 1 => "c");
+/// assert_eq!(map.0, [(1, "c")]);
+///
+/// let empty: SerdeMap<String, &str, IntKey<u8>> = serde_map_with_strategy!(IntKey::default());
+/// assert!(empty.is_empty());
+/// ```
+#[macro_export]
+macro_rules! serde_map_with_strategy {
+    ($ks:expr, $vs:expr $(;)?) => {
+        $crate::SerdeMap::with_strategies($ks, $vs)
+    };
+    ($ks:expr $(;)?) => {
+        $crate::SerdeMap::with_strategies($ks, $crate::Linear)
+    };
+    ($ks:expr, $vs:expr; $($k:expr => $v:expr),+ $(,)?) => {{
+        let mut map = $crate::SerdeMap::with_strategies($ks, $vs);
+        $(map.insert_unchecked($k, $v);)*
+        map
+    }};
+    ($ks:expr; $($k:expr => $v:expr),+ $(,)?) => {
+        $crate::serde_map_with_strategy!($ks, $crate::Linear; $($k => $v),+)
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{IntKey, Linear, SerdeMap};
+    use ::alloc::string::String;
+
+    #[test]
+    #[deny(unused_mut)]
+    fn empty() {
+        let map: SerdeMap<String, u8, IntKey<u8>> = serde_map_with_strategy!(IntKey::default());
+        assert!(map.is_empty());
+        let map: SerdeMap<String, u8, IntKey<u8>> =
+            serde_map_with_strategy!(IntKey::default(), Linear;);
+        assert!(map.is_empty());
+        let map: SerdeMap<String, u8> = serde_map! {};
+        assert!(map.is_empty());
+    }
+}
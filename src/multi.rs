@@ -0,0 +1,289 @@
+use crate::{remove_all, BaseStrategy, DeStrategy, Entries, Linear, SerStrategy, SerdeMap};
+use ::alloc::{collections::BTreeMap, vec::Vec};
+use ::core::{borrow::Borrow, marker::PhantomData, ops::Deref};
+use serde::{
+    de::{Deserialize, Deserializer},
+    ser::{Serialize, SerializeMap, Serializer},
+};
+
+/// [`SerdeMultiMap`] layout, which (de)serializes every entry as a separate map entry:
+/// `{"a": 1, "a": 2}`.
+#[derive(Debug, Clone, Copy)]
+pub struct FlatEntries;
+
+/// [`SerdeMultiMap`] layout, which (de)serializes the values of the same key as a sequence:
+/// `{"a": [1, 2]}`. Keys are serialized in the order of their first occurrence,
+/// they are grouped through a `BTreeMap`, so they have to be `Ord`.
+#[derive(Debug, Clone, Copy)]
+pub struct GroupedEntries;
+
+/// `Map` based on `Vec`, which explicitly allows duplicate keys.
+/// `L` is the serialization layout: [`FlatEntries`] or [`GroupedEntries`].
+/// The key strategy isn't stored, `KS::default()` is used for (de)serialization.
+#[derive(Debug, Clone)]
+pub struct SerdeMultiMap<K, V, KS: BaseStrategy<K> = Linear, L = FlatEntries>(
+    pub Vec<(KS::Des, V)>,
+    PhantomData<(KS, L)>,
+);
+
+impl<K, V, KS: BaseStrategy<K>, L> SerdeMultiMap<K, V, KS, L> {
+    #[inline]
+    pub fn new() -> Self {
+        Self(Vec::new(), PhantomData)
+    }
+
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity), PhantomData)
+    }
+
+    /// Appends the entry, even if the key is already present.
+    #[inline]
+    pub fn insert(&mut self, k: KS::Des, v: V) {
+        self.0.push((k, v));
+    }
+
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[(KS::Des, V)] {
+        &self.0
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the values of all entries with the matching key.
+    #[inline]
+    pub fn get_all<'a, Q>(&'a self, k: &'a Q) -> impl Iterator<Item = &'a V>
+    where
+        KS::Des: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.0
+            .iter()
+            .filter(move |(key, _)| key.borrow() == k)
+            .map(|(_, v)| v)
+    }
+
+    /// Removes all entries with the matching key, returning their values in order.
+    #[inline]
+    pub fn remove_all<Q>(&mut self, k: &Q) -> Vec<V>
+    where
+        KS::Des: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        remove_all(&mut self.0, k)
+    }
+
+    /// Changes the serialization layout.
+    #[inline]
+    pub fn into_layout<L2>(self) -> SerdeMultiMap<K, V, KS, L2> {
+        SerdeMultiMap(self.0, PhantomData)
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, L> Default for SerdeMultiMap<K, V, KS, L> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, L> Deref for SerdeMultiMap<K, V, KS, L> {
+    type Target = [(KS::Des, V)];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, L> IntoIterator for SerdeMultiMap<K, V, KS, L> {
+    type Item = (KS::Des, V);
+    type IntoIter = ::alloc::vec::IntoIter<Self::Item>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, K, V, KS: BaseStrategy<K>, L> IntoIterator for &'a SerdeMultiMap<K, V, KS, L> {
+    type Item = &'a (KS::Des, V);
+    type IntoIter = ::core::slice::Iter<'a, (KS::Des, V)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, L> FromIterator<(KS::Des, V)> for SerdeMultiMap<K, V, KS, L> {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (KS::Des, V)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect(), PhantomData)
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, L> From<Vec<(KS::Des, V)>> for SerdeMultiMap<K, V, KS, L> {
+    #[inline]
+    fn from(data: Vec<(KS::Des, V)>) -> Self {
+        Self(data, PhantomData)
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>, L> From<SerdeMap<K, V, KS, VS>>
+    for SerdeMultiMap<K, VS::Des, KS, L>
+{
+    #[inline]
+    fn from(map: SerdeMap<K, V, KS, VS>) -> Self {
+        Self(map.0, PhantomData)
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K> + Default, VS: BaseStrategy<V> + Default, L>
+    From<SerdeMultiMap<K, VS::Des, KS, L>> for SerdeMap<K, V, KS, VS>
+{
+    #[inline]
+    fn from(map: SerdeMultiMap<K, VS::Des, KS, L>) -> Self {
+        map.0.into()
+    }
+}
+
+impl<K, V: Serialize, KS: SerStrategy<K> + Default> Serialize
+    for SerdeMultiMap<K, V, KS, FlatEntries>
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let human_readable = serializer.is_human_readable();
+        let ks = KS::default();
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (k, v) in self {
+            map.serialize_entry(&ks.serialize_format(k, human_readable)?, v)?;
+        }
+        map.end()
+    }
+}
+
+impl<K, V: Serialize, KS: SerStrategy<K> + Default> Serialize
+    for SerdeMultiMap<K, V, KS, GroupedEntries>
+where
+    KS::Des: Ord,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // values of each key, in the order of its first occurrence
+        let mut index = BTreeMap::new();
+        let mut groups: Vec<(&KS::Des, Vec<&V>)> = Vec::new();
+        for (k, v) in self {
+            let i = *index.entry(k).or_insert_with(|| {
+                groups.push((k, Vec::new()));
+                groups.len() - 1
+            });
+            groups[i].1.push(v);
+        }
+
+        let human_readable = serializer.is_human_readable();
+        let ks = KS::default();
+        let mut map = serializer.serialize_map(Some(groups.len()))?;
+        for (k, values) in &groups {
+            map.serialize_entry(&ks.serialize_format(*k, human_readable)?, values)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, K, V, KS> Deserialize<'de> for SerdeMultiMap<K, V, KS, FlatEntries>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    KS: DeStrategy<K> + Default,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Entries::<K, V, KS>::deserialize(deserializer).map(|e| e.0.into())
+    }
+}
+
+impl<'de, K, V, KS> Deserialize<'de> for SerdeMultiMap<K, V, KS, GroupedEntries>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    KS: DeStrategy<K> + Default,
+    KS::Des: Clone,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let grouped = Entries::<K, Vec<V>, KS>::deserialize(deserializer)?.0;
+        Ok(grouped
+            .into_iter()
+            .flat_map(|(k, vs)| vs.into_iter().map(move |v| (k.clone(), v)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tokens::{to_tokens, Token};
+    use ::alloc::{borrow::ToOwned, string::String, vec};
+
+    #[test]
+    fn grouped_layout() {
+        let map: SerdeMultiMap<String, u8, Linear, GroupedEntries> = vec![
+            ("a".to_owned(), 1),
+            ("b".to_owned(), 2),
+            ("a".to_owned(), 3),
+        ]
+        .into();
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#"{"a":[1,3],"b":[2]}"#);
+
+        let map: SerdeMultiMap<String, u8, Linear, GroupedEntries> =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(map.get_all("a").count(), 2);
+        assert_eq!(
+            serde_json::to_string(&map.into_layout::<FlatEntries>()).unwrap(),
+            r#"{"a":1,"a":3,"b":2}"#
+        );
+    }
+
+    #[test]
+    fn grouped_lengths() {
+        let map: SerdeMultiMap<u8, u8, Linear, GroupedEntries> =
+            vec![(1, 1), (2, 2), (1, 3)].into();
+        assert_eq!(
+            to_tokens(&map).unwrap(),
+            [
+                Token::Map(Some(2)),
+                Token::U64(1),
+                Token::Seq(Some(2)),
+                Token::U64(1),
+                Token::U64(3),
+                Token::End,
+                Token::U64(2),
+                Token::Seq(Some(1)),
+                Token::U64(2),
+                Token::End,
+                Token::End,
+            ]
+        );
+    }
+}
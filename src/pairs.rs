@@ -0,0 +1,60 @@
+use crate::{SerStrategy, SerdeMap, SerdeMapRef};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+/// Serializes the wrapped map as a sequence of `(key, value)` pairs: `[["a", 1], ["b", 2]]`,
+/// still applying the strategies.
+/// [`SerdeMap`] accepts this representation back from self-describing formats.
+/// With the `scylla` feature, it's also written to and read from `list<frozen<tuple<K, V>>>` columns,
+/// which keep the insertion order.
+#[derive(Debug, Clone, Copy)]
+pub struct AsPairs<M>(pub M);
+
+impl<K, V, KS: SerStrategy<K>, VS: SerStrategy<V>> Serialize for AsPairs<&SerdeMap<K, V, KS, VS>> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        AsPairs(self.0.as_map_ref()).serialize(serializer)
+    }
+}
+
+impl<K, V, KS: SerStrategy<K>, VS: SerStrategy<V>> Serialize
+    for AsPairs<SerdeMapRef<'_, K, V, KS, VS>>
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let human_readable = serializer.is_human_readable();
+        let (ks, vs) = (self.0.key_strategy(), self.0.value_strategy());
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for (k, v) in self.0 .0 {
+            seq.serialize_element(&(
+                ks.serialize_format(k, human_readable)?,
+                vs.serialize_format(v, human_readable)?,
+            ))?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::alloc::{string::String, vec};
+
+    #[test]
+    fn pairs() {
+        let map: SerdeMap<String, u8> = vec![("a".into(), 1), ("b".into(), 2)].into();
+        let json = serde_json::to_string(&AsPairs(&map)).unwrap();
+        assert_eq!(json, r#"[["a",1],["b",2]]"#);
+        assert_eq!(
+            serde_json::to_string(&AsPairs(map.as_map_ref())).unwrap(),
+            json
+        );
+        let back: SerdeMap<String, u8> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.0, map.0);
+    }
+}
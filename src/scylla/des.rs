@@ -1,23 +1,133 @@
-use crate::{SerdeMap, SerdeMapStrategy};
+use super::CqlDeStrategy;
+use crate::{SerdeMap, SerdeSet};
 use scylla::{
     deserialize::{
-        value::{DeserializeValue, MapIterator},
+        value::{
+            BuiltinDeserializationError, BuiltinTypeCheckError, BuiltinTypeCheckErrorKind,
+            DeserializeValue, ListlikeIterator, MapIterator, MapTypeCheckErrorKind,
+            SetOrListTypeCheckErrorKind,
+        },
         FrameSlice,
     },
     errors::{DeserializationError, TypeCheckError},
-    frame::response::result::ColumnType,
+    frame::response::result::{CollectionType, ColumnType},
 };
 
-impl<'frame, 'metadata, K, V, KS> DeserializeValue<'frame, 'metadata> for SerdeMap<K, V, KS>
+// copied from scylla
+pub(super) fn mk_typck_err<RustT>(
+    typ: &ColumnType,
+    kind: impl Into<BuiltinTypeCheckErrorKind>,
+) -> TypeCheckError {
+    TypeCheckError::new(BuiltinTypeCheckError {
+        rust_name: ::core::any::type_name::<RustT>(),
+        cql_type: typ.clone().into_owned(),
+        kind: kind.into(),
+    })
+}
+
+/// Checks a map column with the key and value strategies, reporting errors as `RustT`
+pub(super) fn map_type_check<'frame, 'metadata, K, V, KS, VS, RustT>(
+    typ: &ColumnType,
+) -> Result<(), TypeCheckError>
+where
+    KS: CqlDeStrategy<'frame, 'metadata, K>,
+    VS: CqlDeStrategy<'frame, 'metadata, V>,
+{
+    match typ {
+        ColumnType::Collection {
+            frozen: _,
+            typ: CollectionType::Map(ktyp, vtyp),
+        } => {
+            KS::type_check(ktyp).map_err(|err| {
+                mk_typck_err::<RustT>(typ, MapTypeCheckErrorKind::KeyTypeCheckFailed(err))
+            })?;
+            VS::type_check(vtyp).map_err(|err| {
+                mk_typck_err::<RustT>(typ, MapTypeCheckErrorKind::ValueTypeCheckFailed(err))
+            })
+        }
+        _ => Err(mk_typck_err::<RustT>(typ, MapTypeCheckErrorKind::NotMap)),
+    }
+}
+
+// copied from scylla, reports `RustT` instead of the helper type, which has failed
+pub(super) fn typck_error_replace_rust_name<RustT>(mut err: TypeCheckError) -> TypeCheckError {
+    let rust_name = ::core::any::type_name::<RustT>();
+    if let Some(inner) = err.try_downcast_mut::<BuiltinTypeCheckError>() {
+        inner.rust_name = rust_name;
+    } else if let Some(inner) = err.downcast_ref::<BuiltinTypeCheckError>() {
+        if inner.rust_name != rust_name {
+            return TypeCheckError::new(BuiltinTypeCheckError {
+                rust_name,
+                ..inner.clone()
+            });
+        }
+    }
+    err
+}
+
+// copied from scylla, reports `RustT` instead of the helper type, which has failed
+pub(super) fn deser_error_replace_rust_name<RustT>(
+    mut err: DeserializationError,
+) -> DeserializationError {
+    let rust_name = ::core::any::type_name::<RustT>();
+    if let Some(inner) = err.try_downcast_mut::<BuiltinDeserializationError>() {
+        inner.rust_name = rust_name;
+    } else if let Some(inner) = err.downcast_ref::<BuiltinDeserializationError>() {
+        if inner.rust_name != rust_name {
+            return DeserializationError::new(BuiltinDeserializationError {
+                rust_name,
+                ..inner.clone()
+            });
+        }
+    }
+    err
+}
+
+impl<'frame, 'metadata, K, V, KS, VS> DeserializeValue<'frame, 'metadata> for SerdeMap<K, V, KS, VS>
+where
+    KS: CqlDeStrategy<'frame, 'metadata, K> + Default,
+    VS: CqlDeStrategy<'frame, 'metadata, V> + Default,
+{
+    #[inline]
+    fn type_check(typ: &ColumnType) -> Result<(), TypeCheckError> {
+        map_type_check::<K, V, KS, VS, Self>(typ)
+    }
+
+    #[inline]
+    fn deserialize(
+        typ: &'metadata ColumnType<'metadata>,
+        v: Option<FrameSlice<'frame>>,
+    ) -> Result<Self, DeserializationError> {
+        MapIterator::<'frame, 'metadata, KS::Cql, VS::Cql>::deserialize(typ, v)
+            .map_err(deser_error_replace_rust_name::<Self>)?
+            .map(|entry| entry.and_then(|(k, v)| Ok((KS::from_cql(k)?, VS::from_cql(v)?))))
+            .collect::<Result<_, _>>()
+            .map_err(deser_error_replace_rust_name::<Self>)
+    }
+}
+
+/// Read from a `set<T>` or `list<T>` column
+impl<'frame, 'metadata, T, TS> DeserializeValue<'frame, 'metadata> for SerdeSet<T, TS>
 where
-    V: DeserializeValue<'frame, 'metadata>,
-    KS: SerdeMapStrategy<K>,
-    KS::Des: DeserializeValue<'frame, 'metadata>,
+    TS: CqlDeStrategy<'frame, 'metadata, T>,
 {
     #[inline]
     fn type_check(typ: &ColumnType) -> Result<(), TypeCheckError> {
-        MapIterator::<'frame, 'metadata, KS::Des, V>::type_check(typ)
-        // .map_err(typck_error_replace_rust_name::<Self>)
+        match typ {
+            ColumnType::Collection {
+                frozen: _,
+                typ: CollectionType::List(elt) | CollectionType::Set(elt),
+            } => TS::type_check(elt).map_err(|err| {
+                mk_typck_err::<Self>(
+                    typ,
+                    SetOrListTypeCheckErrorKind::ElementTypeCheckFailed(err),
+                )
+            }),
+            _ => Err(mk_typck_err::<Self>(
+                typ,
+                SetOrListTypeCheckErrorKind::NotSetOrList,
+            )),
+        }
     }
 
     #[inline]
@@ -25,8 +135,10 @@ where
         typ: &'metadata ColumnType<'metadata>,
         v: Option<FrameSlice<'frame>>,
     ) -> Result<Self, DeserializationError> {
-        MapIterator::<'frame, 'metadata, KS::Des, V>::deserialize(typ, v)
-            .and_then(|it| it.collect::<Result<_, DeserializationError>>())
-        // .map_err(deser_error_replace_rust_name::<Self>)
+        ListlikeIterator::<'frame, 'metadata, TS::Cql>::deserialize(typ, v)
+            .map_err(deser_error_replace_rust_name::<Self>)?
+            .map(|el| el.and_then(TS::from_cql))
+            .collect::<Result<_, _>>()
+            .map_err(deser_error_replace_rust_name::<Self>)
     }
 }
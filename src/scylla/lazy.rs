@@ -0,0 +1,88 @@
+use super::{
+    des::{deser_error_replace_rust_name, map_type_check},
+    CqlDeStrategy,
+};
+use crate::Linear;
+use ::core::{fmt, marker::PhantomData};
+use scylla::{
+    deserialize::{
+        value::{DeserializeValue, MapIterator},
+        DeserializationError, FrameSlice, TypeCheckError,
+    },
+    frame::response::result::ColumnType,
+};
+
+/// Read from a map column like [`SerdeMap`](crate::SerdeMap), but the entries are converted
+/// by the strategies on demand, while iterating, instead of being collected into a `Vec`.
+pub struct LazySerdeMap<'frame, 'metadata, K, V, KS = Linear, VS = Linear>
+where
+    KS: CqlDeStrategy<'frame, 'metadata, K>,
+    VS: CqlDeStrategy<'frame, 'metadata, V>,
+{
+    iter: MapIterator<'frame, 'metadata, KS::Cql, VS::Cql>,
+    marker: PhantomData<(K, V, KS, VS)>,
+}
+
+impl<'frame, 'metadata, K, V, KS, VS> fmt::Debug for LazySerdeMap<'frame, 'metadata, K, V, KS, VS>
+where
+    KS: CqlDeStrategy<'frame, 'metadata, K>,
+    VS: CqlDeStrategy<'frame, 'metadata, V>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazySerdeMap")
+            .field("len", &self.iter.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'frame, 'metadata, K, V, KS, VS> DeserializeValue<'frame, 'metadata>
+    for LazySerdeMap<'frame, 'metadata, K, V, KS, VS>
+where
+    KS: CqlDeStrategy<'frame, 'metadata, K>,
+    VS: CqlDeStrategy<'frame, 'metadata, V>,
+{
+    #[inline]
+    fn type_check(typ: &ColumnType) -> Result<(), TypeCheckError> {
+        map_type_check::<K, V, KS, VS, Self>(typ)
+    }
+
+    #[inline]
+    fn deserialize(
+        typ: &'metadata ColumnType<'metadata>,
+        v: Option<FrameSlice<'frame>>,
+    ) -> Result<Self, DeserializationError> {
+        MapIterator::deserialize(typ, v)
+            .map(|iter| Self {
+                iter,
+                marker: PhantomData,
+            })
+            .map_err(deser_error_replace_rust_name::<Self>)
+    }
+}
+
+impl<'frame, 'metadata, K, V, KS, VS> Iterator for LazySerdeMap<'frame, 'metadata, K, V, KS, VS>
+where
+    KS: CqlDeStrategy<'frame, 'metadata, K>,
+    VS: CqlDeStrategy<'frame, 'metadata, V>,
+{
+    type Item = Result<(KS::Des, VS::Des), DeserializationError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.iter.next()?;
+        Some(entry.and_then(|(k, v)| Ok((KS::from_cql(k)?, VS::from_cql(v)?))))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'frame, 'metadata, K, V, KS, VS> ExactSizeIterator
+    for LazySerdeMap<'frame, 'metadata, K, V, KS, VS>
+where
+    KS: CqlDeStrategy<'frame, 'metadata, K>,
+    VS: CqlDeStrategy<'frame, 'metadata, V>,
+{
+}
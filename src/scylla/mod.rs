@@ -1,2 +1,518 @@
 mod des;
+mod lazy;
+mod pairs;
+mod row;
 mod ser;
+mod udt;
+
+pub use lazy::LazySerdeMap;
+pub use udt::Udt;
+
+use crate::{BaseStrategy, DisplayFromStr, IntKey, Linear};
+use ::std::{
+    error::Error,
+    fmt,
+    string::{String, ToString},
+};
+use scylla::{
+    deserialize::{value::DeserializeValue, DeserializationError, TypeCheckError},
+    frame::response::result::ColumnType,
+    serialize::value::SerializeValue,
+};
+
+/// CQL write side of a strategy, required by the [`SerdeMap`](crate::SerdeMap) `SerializeValue` impl.
+/// It's separate from [`SerStrategy`](crate::SerStrategy), so e.g. `i64` keys can be written
+/// into a `map<text, ...>` column.
+pub trait CqlSerStrategy<Ser>: BaseStrategy<Ser> {
+    /// CQL value, which is written
+    type CqlRet<'s>: SerializeValue
+    where
+        Self::Des: 's;
+
+    fn to_cql(d: &Self::Des) -> Self::CqlRet<'_>;
+}
+
+/// CQL read side of a strategy, required by the [`SerdeMap`](crate::SerdeMap) `DeserializeValue` impl.
+pub trait CqlDeStrategy<'frame, 'metadata, Ser>: BaseStrategy<Ser> {
+    /// CQL value, which is read
+    type Cql: DeserializeValue<'frame, 'metadata>;
+
+    fn from_cql(c: Self::Cql) -> Result<Self::Des, DeserializationError>;
+
+    /// Checks the CQL type of the key or value up front, so a schema mismatch is reported
+    /// by the type check instead of failing later at parse time.
+    /// Defaults to the type check of [`Self::Cql`](CqlDeStrategy::Cql).
+    #[inline]
+    fn type_check(typ: &ColumnType) -> Result<(), TypeCheckError> {
+        Self::Cql::type_check(typ)
+    }
+}
+
+impl<Ser: SerializeValue> CqlSerStrategy<Ser> for Linear {
+    type CqlRet<'s>
+        = &'s Ser
+    where
+        Ser: 's;
+
+    #[inline(always)]
+    fn to_cql(d: &Self::Des) -> Self::CqlRet<'_> {
+        d
+    }
+}
+
+impl<'frame, 'metadata, Ser: DeserializeValue<'frame, 'metadata>>
+    CqlDeStrategy<'frame, 'metadata, Ser> for Linear
+{
+    type Cql = Ser;
+
+    #[inline(always)]
+    fn from_cql(c: Self::Cql) -> Result<Self::Des, DeserializationError> {
+        Ok(c)
+    }
+}
+
+/// Implements [`CqlSerStrategy`] and [`CqlDeStrategy`] for a strategy, which writes and reads
+/// its stored (`Des`) type as it is. Before the CQL strategy traits every strategy was handled so,
+/// this keeps such strategies working:
+/// ```rust
+/// # use serde_map::{BaseStrategy, Linear, SerdeMap};
+/// struct Tag;
+/// impl BaseStrategy<String> for Tag {
+///     type Des = String;
+/// }
+/// serde_map::cql_as_des!(Tag, String);
+///
+/// fn write(_: impl scylla::serialize::value::SerializeValue) {}
+/// write(SerdeMap::<String, i32, Tag>::with_strategies(Tag, Linear));
+/// ```
+#[macro_export]
+macro_rules! cql_as_des {
+    ($strategy:ty, $ser:ty) => {
+        impl $crate::CqlSerStrategy<$ser> for $strategy
+        where
+            <$strategy as $crate::BaseStrategy<$ser>>::Des: $crate::__private::SerializeValue,
+        {
+            type CqlRet<'s>
+                = &'s <$strategy as $crate::BaseStrategy<$ser>>::Des
+            where
+                <$strategy as $crate::BaseStrategy<$ser>>::Des: 's;
+
+            #[inline(always)]
+            fn to_cql(d: &Self::Des) -> Self::CqlRet<'_> {
+                d
+            }
+        }
+
+        impl<'frame, 'metadata> $crate::CqlDeStrategy<'frame, 'metadata, $ser> for $strategy
+        where
+            <$strategy as $crate::BaseStrategy<$ser>>::Des:
+                $crate::__private::DeserializeValue<'frame, 'metadata>,
+        {
+            type Cql = <$strategy as $crate::BaseStrategy<$ser>>::Des;
+
+            #[inline(always)]
+            fn from_cql(
+                c: Self::Cql,
+            ) -> ::core::result::Result<Self::Des, $crate::__private::DeserializationError> {
+                Ok(c)
+            }
+        }
+    };
+}
+
+/// Strategy failed to parse a CQL value
+#[derive(Debug, Clone)]
+pub struct CqlParseError(pub String);
+
+impl fmt::Display for CqlParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for CqlParseError {}
+
+#[inline]
+fn parse_text<T: ::core::str::FromStr>(s: &str) -> Result<T, DeserializationError>
+where
+    T::Err: fmt::Display,
+{
+    s.parse().map_err(|e| {
+        DeserializationError::new(CqlParseError(::std::format!("invalid value {s:?}: {e}")))
+    })
+}
+
+/// Written as `text`
+impl<T: fmt::Display> CqlSerStrategy<String> for DisplayFromStr<T> {
+    type CqlRet<'s>
+        = String
+    where
+        T: 's;
+
+    #[inline]
+    fn to_cql(d: &Self::Des) -> Self::CqlRet<'_> {
+        d.to_string()
+    }
+}
+
+/// Read from `text`
+impl<'frame, 'metadata, T: ::core::str::FromStr> CqlDeStrategy<'frame, 'metadata, String>
+    for DisplayFromStr<T>
+where
+    T::Err: fmt::Display,
+{
+    type Cql = &'frame str;
+
+    #[inline]
+    fn from_cql(c: Self::Cql) -> Result<Self::Des, DeserializationError> {
+        parse_text(c)
+    }
+}
+
+macro_rules! int_key {
+    ($($t:ty),*) => {$(
+        /// Written as `text`
+        impl CqlSerStrategy<String> for IntKey<$t> {
+            type CqlRet<'s> = String;
+
+            #[inline]
+            fn to_cql(d: &Self::Des) -> Self::CqlRet<'_> {
+                d.to_string()
+            }
+        }
+
+        /// Read from `text`
+        impl<'frame, 'metadata> CqlDeStrategy<'frame, 'metadata, String> for IntKey<$t> {
+            type Cql = &'frame str;
+
+            #[inline]
+            fn from_cql(c: Self::Cql) -> Result<Self::Des, DeserializationError> {
+                parse_text(c)
+            }
+        }
+    )*};
+}
+
+int_key!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SerdeMap;
+    use ::std::{boxed::Box, vec, vec::Vec};
+    use scylla::{
+        cluster::metadata::{CollectionType, NativeType},
+        deserialize::FrameSlice,
+        frame::response::result::ColumnType,
+        serialize::writers::CellWriter,
+    };
+
+    fn map_type(frozen: bool) -> ColumnType<'static> {
+        ColumnType::Collection {
+            frozen,
+            typ: CollectionType::Map(
+                Box::new(ColumnType::Native(NativeType::Text)),
+                Box::new(ColumnType::Native(NativeType::Int)),
+            ),
+        }
+    }
+
+    #[test]
+    fn text_keys() {
+        let typ = map_type(false);
+        let map: SerdeMap<String, i32, IntKey<i64>> = SerdeMap::from_iter([(1, 2), (-3, 4)]);
+        let mut buf = Vec::new();
+        map.serialize(&typ, CellWriter::new(&mut buf)).unwrap();
+
+        let text: SerdeMap<String, i32> =
+            DeserializeValue::deserialize(&typ, Some(FrameSlice::new_borrowed(&buf[4..]))).unwrap();
+        assert_eq!(text.0, vec![("1".into(), 2), ("-3".into(), 4)]);
+
+        let back: SerdeMap<String, i32, IntKey<i64>> =
+            DeserializeValue::deserialize(&typ, Some(FrameSlice::new_borrowed(&buf[4..]))).unwrap();
+        assert_eq!(back.0, map.0);
+    }
+
+    #[derive(Default)]
+    struct Tag;
+
+    impl BaseStrategy<String> for Tag {
+        type Des = String;
+    }
+
+    crate::cql_as_des!(Tag, String);
+
+    #[test]
+    fn as_des() {
+        let typ = map_type(false);
+        let map: SerdeMap<String, i32, Tag> = SerdeMap::from_iter([("a".into(), 1)]);
+        let mut buf = Vec::new();
+        map.serialize(&typ, CellWriter::new(&mut buf)).unwrap();
+        let back: SerdeMap<String, i32, Tag> =
+            DeserializeValue::deserialize(&typ, Some(FrameSlice::new_borrowed(&buf[4..]))).unwrap();
+        assert_eq!(back.0, map.0);
+    }
+
+    #[test]
+    fn frozen() {
+        let typ = map_type(true);
+        assert!(<SerdeMap<String, i32> as DeserializeValue>::type_check(&typ).is_ok());
+        let map: SerdeMap<String, i32> = SerdeMap::from_iter([("a".into(), 1)]);
+        let mut buf = Vec::new();
+        map.serialize(&typ, CellWriter::new(&mut buf)).unwrap();
+        let back: SerdeMap<String, i32> =
+            DeserializeValue::deserialize(&typ, Some(FrameSlice::new_borrowed(&buf[4..]))).unwrap();
+        assert_eq!(back.0, map.0);
+    }
+
+    #[test]
+    fn tuple_list() {
+        let typ = ColumnType::Collection {
+            frozen: false,
+            typ: CollectionType::List(Box::new(ColumnType::Tuple(vec![
+                ColumnType::Native(NativeType::Text),
+                ColumnType::Native(NativeType::Int),
+            ]))),
+        };
+        let map: SerdeMap<String, i32> = SerdeMap::from_iter([("b".into(), 1), ("a".into(), 2)]);
+        let mut buf = Vec::new();
+        crate::AsPairs(&map)
+            .serialize(&typ, CellWriter::new(&mut buf))
+            .unwrap();
+        let back: crate::AsPairs<SerdeMap<String, i32>> =
+            DeserializeValue::deserialize(&typ, Some(FrameSlice::new_borrowed(&buf[4..]))).unwrap();
+        assert_eq!(back.0 .0, map.0);
+    }
+
+    #[test]
+    fn row() {
+        use scylla::{
+            deserialize::row::{ColumnIterator, DeserializeRow},
+            frame::response::result::{ColumnSpec, TableSpec},
+            serialize::{
+                row::{
+                    BuiltinTypeCheckError, BuiltinTypeCheckErrorKind, RowSerializationContext,
+                    SerializeRow,
+                },
+                writers::RowWriter,
+            },
+            value::CqlValue,
+        };
+
+        let table = TableSpec::borrowed("ks", "t");
+        let specs = [
+            ColumnSpec::borrowed("b", ColumnType::Native(NativeType::Int), table.clone()),
+            ColumnSpec::borrowed("a", ColumnType::Native(NativeType::Text), table),
+        ];
+        let map: SerdeMap<String, CqlValue> = SerdeMap::from_iter([
+            ("a".into(), CqlValue::Text("x".into())),
+            ("b".into(), CqlValue::Int(1)),
+        ]);
+        let mut buf = Vec::new();
+        let ctx = RowSerializationContext::from_specs(&specs);
+        SerializeRow::serialize(&map, &ctx, &mut RowWriter::new(&mut buf)).unwrap();
+
+        let mut more = map.clone();
+        more.insert_unchecked("c".into(), CqlValue::Int(2));
+        let err =
+            SerializeRow::serialize(&more, &ctx, &mut RowWriter::new(&mut Vec::new())).unwrap_err();
+        let err = err.downcast_ref::<BuiltinTypeCheckError>().unwrap();
+        assert!(
+            matches!(&err.kind, BuiltinTypeCheckErrorKind::NoColumnWithName { name } if name == "c")
+        );
+
+        let row = ColumnIterator::new(&specs, FrameSlice::new_borrowed(&buf));
+        let back: SerdeMap<String, CqlValue> = DeserializeRow::deserialize(row).unwrap();
+        assert_eq!(
+            back.0,
+            vec![
+                ("b".into(), CqlValue::Int(1)),
+                ("a".into(), CqlValue::Text("x".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn udt() {
+        use ::std::sync::Arc;
+        use scylla::{frame::response::result::UserDefinedType, value::CqlValue};
+
+        let typ = ColumnType::UserDefinedType {
+            frozen: false,
+            definition: Arc::new(UserDefinedType {
+                name: "point".into(),
+                keyspace: "ks".into(),
+                field_types: vec![
+                    ("y".into(), ColumnType::Native(NativeType::Int)),
+                    ("x".into(), ColumnType::Native(NativeType::Text)),
+                    ("z".into(), ColumnType::Native(NativeType::Int)),
+                ],
+            }),
+        };
+        // `z` is missing from the serialized value
+        let buf = [0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 1, b'a'];
+        let udt: Udt<SerdeMap<String, Option<CqlValue>>> =
+            DeserializeValue::deserialize(&typ, Some(FrameSlice::new_borrowed(&buf))).unwrap();
+        assert_eq!(
+            udt.0 .0,
+            vec![
+                ("y".into(), Some(CqlValue::Int(1))),
+                ("x".into(), Some(CqlValue::Text("a".into()))),
+                ("z".into(), None),
+            ]
+        );
+        assert!(<Udt<SerdeMap<String, i32>> as DeserializeValue>::type_check(&typ).is_err());
+    }
+
+    #[test]
+    fn udt_field_error() {
+        use ::std::sync::Arc;
+        use scylla::{
+            deserialize::value::{BuiltinDeserializationError, BuiltinDeserializationErrorKind},
+            frame::response::result::UserDefinedType,
+        };
+
+        let typ = ColumnType::UserDefinedType {
+            frozen: false,
+            definition: Arc::new(UserDefinedType {
+                name: "counter".into(),
+                keyspace: "ks".into(),
+                field_types: vec![("n".into(), ColumnType::Native(NativeType::Int))],
+            }),
+        };
+        // `n` has 1 byte instead of 4
+        let buf = [0, 0, 0, 1, 1];
+        type Counter = Udt<SerdeMap<String, i32>>;
+        let err =
+            <Counter as DeserializeValue>::deserialize(&typ, Some(FrameSlice::new_borrowed(&buf)))
+                .unwrap_err();
+        let err = err.downcast_ref::<BuiltinDeserializationError>().unwrap();
+        assert_eq!(err.rust_name, ::core::any::type_name::<Counter>());
+        assert!(matches!(
+            err.kind,
+            BuiltinDeserializationErrorKind::ByteLengthMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn error_rust_name() {
+        use scylla::deserialize::value::{BuiltinDeserializationError, BuiltinTypeCheckError};
+
+        let typ = ColumnType::Native(NativeType::Int);
+        let err = <SerdeMap<String, i32> as DeserializeValue>::type_check(&typ).unwrap_err();
+        let err = err.downcast_ref::<BuiltinTypeCheckError>().unwrap();
+        assert_eq!(
+            err.rust_name,
+            ::core::any::type_name::<SerdeMap<String, i32>>()
+        );
+
+        // one entry is declared, but missing
+        let typ = map_type(false);
+        let slice = FrameSlice::new_borrowed(&[0, 0, 0, 1]);
+        let err = <SerdeMap<String, i32> as DeserializeValue>::deserialize(&typ, Some(slice))
+            .unwrap_err();
+        let err = err.downcast_ref::<BuiltinDeserializationError>().unwrap();
+        assert_eq!(
+            err.rust_name,
+            ::core::any::type_name::<SerdeMap<String, i32>>()
+        );
+    }
+
+    #[test]
+    fn lazy() {
+        let typ = map_type(false);
+        let map: SerdeMap<String, i32> = SerdeMap::from_iter([("1".into(), 2), ("x".into(), 4)]);
+        let mut buf = Vec::new();
+        map.serialize(&typ, CellWriter::new(&mut buf)).unwrap();
+
+        let mut lazy: LazySerdeMap<String, i32, IntKey<i64>> =
+            DeserializeValue::deserialize(&typ, Some(FrameSlice::new_borrowed(&buf[4..]))).unwrap();
+        assert_eq!(lazy.len(), 2);
+        assert_eq!(lazy.next().unwrap().unwrap(), (1, 2));
+        assert!(lazy.next().unwrap().is_err());
+        assert!(lazy.next().is_none());
+    }
+
+    #[test]
+    fn borrowed() {
+        let typ = map_type(false);
+        let map: SerdeMap<String, i32, IntKey<i64>> = SerdeMap::from_iter([(1, 2)]);
+        let (mut by_ref, mut view) = (Vec::new(), Vec::new());
+        SerializeValue::serialize(&&map, &typ, CellWriter::new(&mut by_ref)).unwrap();
+        map.as_map_ref()
+            .serialize(&typ, CellWriter::new(&mut view))
+            .unwrap();
+        assert_eq!(by_ref, view);
+    }
+
+    #[test]
+    fn set_and_list() {
+        let set: crate::SerdeSet<String, IntKey<i64>> = crate::SerdeSet::from_iter([3, 1]);
+        for typ in [CollectionType::Set, CollectionType::List] {
+            let typ = ColumnType::Collection {
+                frozen: false,
+                typ: typ(Box::new(ColumnType::Native(NativeType::Text))),
+            };
+            let mut buf = Vec::new();
+            set.serialize(&typ, CellWriter::new(&mut buf)).unwrap();
+            let text: crate::SerdeSet<String> =
+                DeserializeValue::deserialize(&typ, Some(FrameSlice::new_borrowed(&buf[4..])))
+                    .unwrap();
+            assert_eq!(text.0, ["3", "1"]);
+            let back: crate::SerdeSet<String, IntKey<i64>> =
+                DeserializeValue::deserialize(&typ, Some(FrameSlice::new_borrowed(&buf[4..])))
+                    .unwrap();
+            assert_eq!(back.0, set.0);
+        }
+    }
+
+    #[test]
+    fn strategy_type_check() {
+        use scylla::{
+            deserialize::value::{BuiltinTypeCheckError, BuiltinTypeCheckErrorKind},
+            value::CqlValue,
+        };
+
+        #[derive(Default)]
+        struct Text;
+
+        impl BaseStrategy<String> for Text {
+            type Des = String;
+        }
+
+        impl<'frame, 'metadata> CqlDeStrategy<'frame, 'metadata, String> for Text {
+            type Cql = CqlValue;
+
+            fn from_cql(c: Self::Cql) -> Result<Self::Des, DeserializationError> {
+                c.into_string()
+                    .ok_or_else(|| DeserializationError::new(CqlParseError("not text".into())))
+            }
+
+            fn type_check(typ: &ColumnType) -> Result<(), TypeCheckError> {
+                match typ {
+                    ColumnType::Native(NativeType::Text) => Ok(()),
+                    _ => Err(TypeCheckError::new(CqlParseError("not text".into()))),
+                }
+            }
+        }
+
+        assert!(
+            <SerdeMap<String, i32, Text> as DeserializeValue>::type_check(&map_type(false)).is_ok()
+        );
+        let typ = ColumnType::Collection {
+            frozen: false,
+            typ: CollectionType::Map(
+                Box::new(ColumnType::Native(NativeType::Int)),
+                Box::new(ColumnType::Native(NativeType::Int)),
+            ),
+        };
+        let err = <SerdeMap<String, i32, Text> as DeserializeValue>::type_check(&typ).unwrap_err();
+        let err = err.downcast_ref::<BuiltinTypeCheckError>().unwrap();
+        assert!(matches!(
+            err.kind,
+            BuiltinTypeCheckErrorKind::MapError(
+                scylla::deserialize::value::MapTypeCheckErrorKind::KeyTypeCheckFailed(_)
+            )
+        ));
+    }
+}
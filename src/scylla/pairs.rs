@@ -0,0 +1,102 @@
+use super::{
+    des::{deser_error_replace_rust_name, mk_typck_err, typck_error_replace_rust_name},
+    CqlDeStrategy, CqlSerStrategy,
+};
+use crate::{AsPairs, SerdeMap};
+use ::std::vec::Vec;
+use scylla::{
+    deserialize::{
+        value::{DeserializeValue, SetOrListTypeCheckErrorKind, TupleTypeCheckErrorKind},
+        DeserializationError, FrameSlice, TypeCheckError,
+    },
+    frame::response::result::{CollectionType, ColumnType},
+    serialize::{
+        value::SerializeValue,
+        writers::{CellWriter, WrittenCellProof},
+        SerializationError,
+    },
+};
+
+/// Written into a `list<frozen<tuple<K, V>>>` column, which keeps the order of entries
+impl<K, V, KS: CqlSerStrategy<K>, VS: CqlSerStrategy<V>> SerializeValue
+    for AsPairs<&SerdeMap<K, V, KS, VS>>
+{
+    fn serialize<'b>(
+        &self,
+        typ: &ColumnType,
+        writer: CellWriter<'b>,
+    ) -> Result<WrittenCellProof<'b>, SerializationError> {
+        let pairs: Vec<_> = self
+            .0
+             .0
+            .iter()
+            .map(|(k, v)| (KS::to_cql(k), VS::to_cql(v)))
+            .collect();
+        pairs.serialize(typ, writer)
+    }
+}
+
+impl<K, V, KS: CqlSerStrategy<K>, VS: CqlSerStrategy<V>> SerializeValue
+    for AsPairs<SerdeMap<K, V, KS, VS>>
+{
+    #[inline]
+    fn serialize<'b>(
+        &self,
+        typ: &ColumnType,
+        writer: CellWriter<'b>,
+    ) -> Result<WrittenCellProof<'b>, SerializationError> {
+        AsPairs(&self.0).serialize(typ, writer)
+    }
+}
+
+/// Read from a `list<frozen<tuple<K, V>>>` column
+impl<'frame, 'metadata, K, V, KS, VS> DeserializeValue<'frame, 'metadata>
+    for AsPairs<SerdeMap<K, V, KS, VS>>
+where
+    KS: CqlDeStrategy<'frame, 'metadata, K> + Default,
+    VS: CqlDeStrategy<'frame, 'metadata, V> + Default,
+{
+    #[inline]
+    fn type_check(typ: &ColumnType) -> Result<(), TypeCheckError> {
+        Vec::<(KS::Cql, VS::Cql)>::type_check(typ)
+            .map_err(typck_error_replace_rust_name::<Self>)?;
+        // the shape is checked above, the strategies check the tuple fields
+        let ColumnType::Collection {
+            frozen: _,
+            typ: CollectionType::List(elt) | CollectionType::Set(elt),
+        } = typ
+        else {
+            return Ok(());
+        };
+        let ColumnType::Tuple(fields) = elt.as_ref() else {
+            return Ok(());
+        };
+        let field_err = |position, err| {
+            let err = mk_typck_err::<(KS::Cql, VS::Cql)>(
+                elt,
+                TupleTypeCheckErrorKind::FieldTypeCheckFailed { position, err },
+            );
+            mk_typck_err::<Self>(
+                typ,
+                SetOrListTypeCheckErrorKind::ElementTypeCheckFailed(err),
+            )
+        };
+        if let [ktyp, vtyp] = fields.as_slice() {
+            KS::type_check(ktyp).map_err(|err| field_err(0, err))?;
+            VS::type_check(vtyp).map_err(|err| field_err(1, err))?;
+        }
+        Ok(())
+    }
+
+    fn deserialize(
+        typ: &'metadata ColumnType<'metadata>,
+        v: Option<FrameSlice<'frame>>,
+    ) -> Result<Self, DeserializationError> {
+        Vec::<(KS::Cql, VS::Cql)>::deserialize(typ, v)
+            .map_err(deser_error_replace_rust_name::<Self>)?
+            .into_iter()
+            .map(|(k, v)| Ok((KS::from_cql(k)?, VS::from_cql(v)?)))
+            .collect::<Result<_, _>>()
+            .map(AsPairs)
+    }
+}
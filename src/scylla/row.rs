@@ -0,0 +1,139 @@
+use super::{CqlDeStrategy, CqlSerStrategy};
+use crate::{Linear, SerdeMap};
+use ::core::borrow::Borrow;
+use ::std::{
+    borrow::ToOwned,
+    collections::{HashMap, HashSet},
+    string::String,
+    vec::Vec,
+};
+use scylla::{
+    deserialize::{
+        row::{
+            BuiltinDeserializationError, BuiltinDeserializationErrorKind,
+            BuiltinTypeCheckError as DeTypeCheckError,
+            BuiltinTypeCheckErrorKind as DeTypeCheckErrorKind, ColumnIterator, DeserializeRow,
+        },
+        value::DeserializeValue,
+        DeserializationError, TypeCheckError,
+    },
+    frame::response::result::ColumnSpec,
+    serialize::{
+        row::{
+            BuiltinSerializationError, BuiltinSerializationErrorKind, BuiltinTypeCheckError,
+            BuiltinTypeCheckErrorKind, RowSerializationContext, SerializeRow,
+        },
+        value::SerializeValue,
+        writers::RowWriter,
+        SerializationError,
+    },
+};
+
+/// Binds each entry as a named value, keys must match the column names of the statement
+impl<K, V, KS, VS> SerializeRow for SerdeMap<K, V, KS, VS>
+where
+    KS: CqlSerStrategy<K>,
+    KS::Des: Borrow<str>,
+    VS: CqlSerStrategy<V>,
+{
+    fn serialize(
+        &self,
+        ctx: &RowSerializationContext<'_>,
+        writer: &mut RowWriter,
+    ) -> Result<(), SerializationError> {
+        let rust_name = ::core::any::type_name::<Self>();
+        let columns = ctx.columns();
+        // the first value of each key, like `get`
+        let mut values = HashMap::with_capacity(self.len());
+        for (k, v) in self.iter() {
+            values.entry(k.borrow()).or_insert(v);
+        }
+        for col in columns {
+            let Some(&v) = values.get(col.name()) else {
+                return Err(SerializationError::new(BuiltinTypeCheckError {
+                    rust_name,
+                    kind: BuiltinTypeCheckErrorKind::ValueMissingForColumn {
+                        name: col.name().to_owned(),
+                    },
+                }));
+            };
+            VS::to_cql(v)
+                .serialize(col.typ(), writer.make_cell_writer())
+                .map_err(|err| {
+                    SerializationError::new(BuiltinSerializationError {
+                        rust_name,
+                        kind: BuiltinSerializationErrorKind::ColumnSerializationFailed {
+                            name: col.name().to_owned(),
+                            err,
+                        },
+                    })
+                })?;
+        }
+
+        // every column has a key, so the keys are covered unless there are more of them
+        let names: HashSet<&str> = columns.iter().map(ColumnSpec::name).collect();
+        if names.len() == values.len() {
+            return Ok(());
+        }
+        // the first key (in the map order) without a column
+        if let Some((k, _)) = self.iter().find(|(k, _)| !names.contains((*k).borrow())) {
+            return Err(SerializationError::new(BuiltinTypeCheckError {
+                rust_name,
+                kind: BuiltinTypeCheckErrorKind::NoColumnWithName {
+                    name: k.borrow().to_owned(),
+                },
+            }));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        SerdeMap::is_empty(self)
+    }
+}
+
+/// Collects the columns of a row by their names, in the column order.
+/// `SerdeMap<String, CqlValue>` accepts any row (`Option<CqlValue>` to allow nulls).
+impl<'frame, 'metadata, V, VS> DeserializeRow<'frame, 'metadata> for SerdeMap<String, V, Linear, VS>
+where
+    VS: CqlDeStrategy<'frame, 'metadata, V> + Default,
+{
+    fn type_check(specs: &[ColumnSpec]) -> Result<(), TypeCheckError> {
+        for (column_index, spec) in specs.iter().enumerate() {
+            VS::type_check(spec.typ()).map_err(|err| {
+                TypeCheckError::new(DeTypeCheckError {
+                    rust_name: ::core::any::type_name::<Self>(),
+                    cql_types: specs.iter().map(|s| s.typ().clone().into_owned()).collect(),
+                    kind: DeTypeCheckErrorKind::ColumnTypeCheckFailed {
+                        column_index,
+                        column_name: spec.name().to_owned(),
+                        err,
+                    },
+                })
+            })?;
+        }
+        Ok(())
+    }
+
+    fn deserialize(row: ColumnIterator<'frame, 'metadata>) -> Result<Self, DeserializationError> {
+        let mut entries = Vec::with_capacity(row.size_hint().0);
+        for column in row {
+            let column = column?;
+            let value = VS::Cql::deserialize(column.spec.typ(), column.slice)
+                .and_then(VS::from_cql)
+                .map_err(|err| {
+                    DeserializationError::new(BuiltinDeserializationError {
+                        rust_name: ::core::any::type_name::<Self>(),
+                        kind: BuiltinDeserializationErrorKind::ColumnDeserializationFailed {
+                            column_index: column.index,
+                            column_name: column.spec.name().to_owned(),
+                            err,
+                        },
+                    })
+                })?;
+            entries.push((column.spec.name().to_owned(), value));
+        }
+        Ok(entries.into())
+    }
+}
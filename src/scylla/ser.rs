@@ -1,4 +1,5 @@
-use crate::{SerdeMap, SerdeMapStrategy};
+use super::CqlSerStrategy;
+use crate::{SerdeMap, SerdeMapRef, SerdeSet};
 use scylla::{
     cluster::metadata::CollectionType,
     frame::response::result::ColumnType,
@@ -6,7 +7,7 @@ use scylla::{
         value::{
             BuiltinSerializationError, BuiltinSerializationErrorKind, BuiltinTypeCheckError,
             BuiltinTypeCheckErrorKind, MapSerializationErrorKind, MapTypeCheckErrorKind,
-            SerializeValue,
+            SerializeValue, SetOrListSerializationErrorKind, SetOrListTypeCheckErrorKind,
         },
         writers::{CellWriter, WrittenCellProof},
         SerializationError,
@@ -39,18 +40,19 @@ fn mk_typck_err_named(
     })
 }
 
-// copied from scylla
+// copied from scylla, takes the converted entries by value
 #[inline]
-fn serialize_mapping<'t, 'b, K: SerializeValue + 't, V: SerializeValue + 't>(
+fn serialize_mapping<'b, K: SerializeValue, V: SerializeValue>(
     rust_name: &'static str,
     len: usize,
-    iter: impl Iterator<Item = &'t (K, V)>,
+    iter: impl Iterator<Item = (K, V)>,
     typ: &ColumnType,
     writer: CellWriter<'b>,
 ) -> Result<WrittenCellProof<'b>, SerializationError> {
     let (ktyp, vtyp) = match typ {
+        // frozen maps have the same encoding
         ColumnType::Collection {
-            frozen: false,
+            frozen: _,
             typ: CollectionType::Map(k, v),
         } => (k, v),
         _ => {
@@ -70,14 +72,14 @@ fn serialize_mapping<'t, 'b, K: SerializeValue + 't, V: SerializeValue + 't>(
     builder.append_bytes(&element_count.to_be_bytes());
 
     for (k, v) in iter {
-        K::serialize(k, ktyp, builder.make_sub_writer()).map_err(|err| {
+        K::serialize(&k, ktyp, builder.make_sub_writer()).map_err(|err| {
             mk_ser_err_named(
                 rust_name,
                 typ,
                 MapSerializationErrorKind::KeySerializationFailed(err),
             )
         })?;
-        V::serialize(v, vtyp, builder.make_sub_writer()).map_err(|err| {
+        V::serialize(&v, vtyp, builder.make_sub_writer()).map_err(|err| {
             mk_ser_err_named(
                 rust_name,
                 typ,
@@ -91,9 +93,74 @@ fn serialize_mapping<'t, 'b, K: SerializeValue + 't, V: SerializeValue + 't>(
         .map_err(|_| mk_ser_err_named(rust_name, typ, BuiltinSerializationErrorKind::SizeOverflow))
 }
 
-impl<K, V: SerializeValue, KS: SerdeMapStrategy<K>> SerializeValue for SerdeMap<K, V, KS>
-where
-    KS::Des: SerializeValue,
+// copied from scylla, takes the converted elements by value
+#[inline]
+fn serialize_sequence<'b, T: SerializeValue>(
+    rust_name: &'static str,
+    len: usize,
+    iter: impl Iterator<Item = T>,
+    typ: &ColumnType,
+    writer: CellWriter<'b>,
+) -> Result<WrittenCellProof<'b>, SerializationError> {
+    let elt = match typ {
+        ColumnType::Collection {
+            frozen: _,
+            typ: CollectionType::List(elt) | CollectionType::Set(elt),
+        } => elt,
+        _ => {
+            return Err(mk_typck_err_named(
+                rust_name,
+                typ,
+                SetOrListTypeCheckErrorKind::NotSetOrList,
+            ));
+        }
+    };
+
+    let mut builder = writer.into_value_builder();
+
+    let element_count: i32 = len.try_into().map_err(|_| {
+        mk_ser_err_named(
+            rust_name,
+            typ,
+            SetOrListSerializationErrorKind::TooManyElements,
+        )
+    })?;
+    builder.append_bytes(&element_count.to_be_bytes());
+
+    for el in iter {
+        T::serialize(&el, elt, builder.make_sub_writer()).map_err(|err| {
+            mk_ser_err_named(
+                rust_name,
+                typ,
+                SetOrListSerializationErrorKind::ElementSerializationFailed(err),
+            )
+        })?;
+    }
+
+    builder
+        .finish()
+        .map_err(|_| mk_ser_err_named(rust_name, typ, BuiltinSerializationErrorKind::SizeOverflow))
+}
+
+impl<K, V, KS: CqlSerStrategy<K>, VS: CqlSerStrategy<V>> SerializeValue for SerdeMap<K, V, KS, VS> {
+    fn serialize<'b>(
+        &self,
+        typ: &ColumnType,
+        writer: CellWriter<'b>,
+    ) -> Result<WrittenCellProof<'b>, SerializationError> {
+        serialize_mapping(
+            ::core::any::type_name::<Self>(),
+            self.len(),
+            self.0.iter().map(|(k, v)| (KS::to_cql(k), VS::to_cql(v))),
+            typ,
+            writer,
+        )
+    }
+}
+
+/// `&SerdeMap` is written through the `&T` impl of scylla
+impl<K, V, KS: CqlSerStrategy<K>, VS: CqlSerStrategy<V>> SerializeValue
+    for SerdeMapRef<'_, K, V, KS, VS>
 {
     fn serialize<'b>(
         &self,
@@ -101,9 +168,26 @@ where
         writer: CellWriter<'b>,
     ) -> Result<WrittenCellProof<'b>, SerializationError> {
         serialize_mapping(
-            std::any::type_name::<Self>(),
+            ::core::any::type_name::<Self>(),
+            self.len(),
+            self.iter().map(|(k, v)| (KS::to_cql(k), VS::to_cql(v))),
+            typ,
+            writer,
+        )
+    }
+}
+
+/// Written into a `set<T>` or `list<T>` column
+impl<T, TS: CqlSerStrategy<T>> SerializeValue for SerdeSet<T, TS> {
+    fn serialize<'b>(
+        &self,
+        typ: &ColumnType,
+        writer: CellWriter<'b>,
+    ) -> Result<WrittenCellProof<'b>, SerializationError> {
+        serialize_sequence(
+            ::core::any::type_name::<Self>(),
             self.len(),
-            self.0.iter(),
+            self.iter().map(TS::to_cql),
             typ,
             writer,
         )
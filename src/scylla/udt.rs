@@ -0,0 +1,60 @@
+use super::{
+    des::{deser_error_replace_rust_name, mk_typck_err},
+    CqlDeStrategy,
+};
+use crate::{Linear, SerdeMap};
+use ::std::{borrow::ToOwned, string::String, vec::Vec};
+use scylla::{
+    deserialize::{
+        value::{DeserializeValue, UdtIterator, UdtTypeCheckErrorKind},
+        DeserializationError, FrameSlice, TypeCheckError,
+    },
+    frame::response::result::ColumnType,
+};
+
+/// Reads a user defined type as its `field name -> value` entries, in the declaration order.
+/// `Udt<SerdeMap<String, CqlValue>>` accepts any UDT (`Option<CqlValue>` to allow nulls),
+/// fields missing from the serialized value are read as nulls.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Udt<M>(pub M);
+
+impl<'frame, 'metadata, V, VS> DeserializeValue<'frame, 'metadata>
+    for Udt<SerdeMap<String, V, Linear, VS>>
+where
+    VS: CqlDeStrategy<'frame, 'metadata, V> + Default,
+{
+    fn type_check(typ: &ColumnType) -> Result<(), TypeCheckError> {
+        let ColumnType::UserDefinedType { definition, .. } = typ else {
+            return Err(mk_typck_err::<Self>(typ, UdtTypeCheckErrorKind::NotUdt));
+        };
+        for (name, field_typ) in definition.field_types.iter() {
+            VS::type_check(field_typ).map_err(|err| {
+                mk_typck_err::<Self>(
+                    typ,
+                    UdtTypeCheckErrorKind::FieldTypeCheckFailed {
+                        field_name: name.as_ref().to_owned(),
+                        err,
+                    },
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    fn deserialize(
+        typ: &'metadata ColumnType<'metadata>,
+        v: Option<FrameSlice<'frame>>,
+    ) -> Result<Self, DeserializationError> {
+        let fields =
+            UdtIterator::deserialize(typ, v).map_err(deser_error_replace_rust_name::<Self>)?;
+        let mut entries = Vec::with_capacity(fields.len());
+        for ((name, field_typ), raw) in fields {
+            let raw = raw.map_err(deser_error_replace_rust_name::<Self>)?;
+            let value = VS::Cql::deserialize(field_typ, raw.flatten())
+                .and_then(VS::from_cql)
+                .map_err(deser_error_replace_rust_name::<Self>)?;
+            entries.push((name.as_ref().to_owned(), value));
+        }
+        Ok(Self(entries.into()))
+    }
+}
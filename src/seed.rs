@@ -0,0 +1,191 @@
+use crate::{
+    access::{deserialize_entries, EntryAccess, VisitEntries},
+    cautious_capacity, BaseStrategy, DeStrategy, Linear, SerdeMap,
+};
+use ::alloc::vec::Vec;
+use ::core::marker::PhantomData;
+use serde::de::{Deserialize, DeserializeSeed, Deserializer, Error};
+
+/// [`DeserializeSeed`] for [`SerdeMap`], which carries external state into deserialization:
+/// a capacity hint (used if larger than the format's hint), an entry limit,
+/// the strategy instances and a seed for values.
+/// The value seed is cloned for each entry, `PhantomData<V>` deserializes `V` as usual.
+pub struct SerdeMapSeed<
+    K,
+    V,
+    KS: BaseStrategy<K> = Linear,
+    VS: BaseStrategy<V> = Linear,
+    VSeed = PhantomData<V>,
+> {
+    capacity: usize,
+    limit: usize,
+    strategies: (KS, VS),
+    value_seed: VSeed,
+    human_readable: bool,
+    marker: PhantomData<(K, V)>,
+}
+
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> SerdeMapSeed<K, V, KS, VS> {
+    #[inline]
+    pub fn new(key_strategy: KS, value_strategy: VS) -> Self {
+        Self {
+            capacity: 0,
+            limit: usize::MAX,
+            strategies: (key_strategy, value_strategy),
+            value_seed: PhantomData,
+            human_readable: true,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>, VSeed> SerdeMapSeed<K, V, KS, VS, VSeed> {
+    /// Preallocates the inner `Vec`
+    #[inline]
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Fails the deserialization if the input contains more than `limit` entries,
+    /// protecting from unbounded allocation on untrusted input
+    #[inline]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Deserializes each value with a clone of `value_seed`
+    #[inline]
+    pub fn value_seed<S>(self, value_seed: S) -> SerdeMapSeed<K, V, KS, VS, S> {
+        SerdeMapSeed {
+            capacity: self.capacity,
+            limit: self.limit,
+            strategies: self.strategies,
+            value_seed,
+            human_readable: self.human_readable,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K> + Default, VS: BaseStrategy<V> + Default> Default
+    for SerdeMapSeed<K, V, KS, VS>
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(KS::default(), VS::default())
+    }
+}
+
+impl<'de, K, V, KS, VS, VSeed> DeserializeSeed<'de> for SerdeMapSeed<K, V, KS, VS, VSeed>
+where
+    K: Deserialize<'de>,
+    KS: DeStrategy<K>,
+    VS: DeStrategy<V>,
+    VSeed: DeserializeSeed<'de, Value = V> + Clone,
+{
+    type Value = SerdeMap<K, V, KS, VS>;
+
+    fn deserialize<D>(mut self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.human_readable = deserializer.is_human_readable();
+        deserialize_entries(deserializer, self)
+    }
+}
+
+impl<'de, K, V, KS, VS, VSeed> VisitEntries<'de> for SerdeMapSeed<K, V, KS, VS, VSeed>
+where
+    K: Deserialize<'de>,
+    KS: DeStrategy<K>,
+    VS: DeStrategy<V>,
+    VSeed: DeserializeSeed<'de, Value = V> + Clone,
+{
+    type Value = SerdeMap<K, V, KS, VS>;
+
+    #[inline]
+    fn visit_entries<A: EntryAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let capacity = cautious_capacity::<(KS::Des, VS::Des)>(access.size_hint())
+            .max(self.capacity)
+            .min(self.limit);
+        let mut values = SerdeMap(Vec::with_capacity(capacity), self.strategies);
+
+        let mut index = 0;
+        let value_seed = || self.value_seed.clone();
+        while let Some((key, value)) = access.next_entry_seed(PhantomData, value_seed())? {
+            if index == self.limit {
+                return Err(Error::custom(format_args!(
+                    "too many entries, the limit is {}",
+                    self.limit
+                )));
+            }
+            let k = values
+                .1
+                 .0
+                .deserialize_format(index, key, self.human_readable)?;
+            let v = values
+                .1
+                 .1
+                .deserialize_format(index, value, self.human_readable)?;
+            if let (Some(k), Some(v)) = (k, v) {
+                values.insert_unchecked(k, v)
+            }
+            index += 1;
+        }
+
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::alloc::{string::ToString, vec};
+
+    #[derive(Clone)]
+    struct Offset(u8);
+
+    impl<'de> DeserializeSeed<'de> for Offset {
+        type Value = u8;
+
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<u8, D::Error> {
+            u8::deserialize(deserializer).map(|v| v + self.0)
+        }
+    }
+
+    #[test]
+    fn seed() {
+        let seed = SerdeMapSeed::<u8, u8>::default().capacity(8);
+        let mut de = serde_json::Deserializer::from_str(r#"{"1":2,"3":4}"#);
+        let map = seed.deserialize(&mut de).unwrap();
+        assert_eq!(map.0, vec![(1, 2), (3, 4)]);
+        assert!(map.0.capacity() >= 8);
+
+        let seed = SerdeMapSeed::<u8, u8>::default().value_seed(Offset(10));
+        let mut de = serde_json::Deserializer::from_str(r#"{"1":2,"3":4}"#);
+        let map = seed.deserialize(&mut de).unwrap();
+        assert_eq!(map.0, vec![(1, 12), (3, 14)]);
+
+        let seed = SerdeMapSeed::<u8, u8>::default().limit(1);
+        let mut de = serde_json::Deserializer::from_str(r#"{"1":2,"3":4}"#);
+        let err = seed.deserialize(&mut de).unwrap_err().to_string();
+        assert!(err.starts_with("too many entries, the limit is 1"), "{err}");
+    }
+
+    #[test]
+    fn seed_inputs() {
+        let seed = SerdeMapSeed::<u8, u8>::default().value_seed(Offset(10));
+        let mut de = serde_json::Deserializer::from_str(r#"[[1,2],[3,4]]"#);
+        assert_eq!(seed.deserialize(&mut de).unwrap().0, vec![(1, 12), (3, 14)]);
+
+        let seed = SerdeMapSeed::<u8, u8>::default().limit(1);
+        let mut de = serde_json::Deserializer::from_str(r#"[[1,2],[3,4]]"#);
+        assert!(seed.deserialize(&mut de).is_err());
+
+        let seed = SerdeMapSeed::<u8, u8>::default();
+        let mut de = serde_json::Deserializer::from_str("null");
+        assert!(seed.deserialize(&mut de).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,261 @@
+use crate::{cautious_capacity, BaseStrategy, DeStrategy, Linear, SerStrategy};
+use ::alloc::collections::BTreeSet;
+use ::alloc::vec::Vec;
+use ::core::{borrow::Borrow, fmt, marker::PhantomData, ops::Deref};
+#[cfg(feature = "std")]
+use ::std::collections::HashSet;
+use serde::{
+    de::{Deserialize, Deserializer, SeqAccess, Visitor},
+    ser::{Serialize, SerializeSeq, Serializer},
+};
+
+/// `Set` based on `Vec` for serialization purposes, serialized as a sequence.
+/// Elements are processed by the strategy `TS`, the same way as [`crate::SerdeMap`] keys,
+/// but the strategy isn't stored: `TS::default()` is used.
+#[derive(Debug, Clone)]
+pub struct SerdeSet<T, TS: BaseStrategy<T> = Linear>(pub Vec<TS::Des>, PhantomData<TS>);
+
+impl<T, TS: BaseStrategy<T>> SerdeSet<T, TS> {
+    #[inline]
+    pub fn new() -> Self {
+        Self(Vec::new(), PhantomData)
+    }
+
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity), PhantomData)
+    }
+
+    #[inline]
+    pub fn insert_unchecked(&mut self, v: TS::Des) {
+        self.0.push(v);
+    }
+
+    /// Appends the element, if it's not present yet.
+    /// Returns whether it was inserted.
+    #[inline]
+    pub fn insert(&mut self, v: TS::Des) -> bool
+    where
+        TS::Des: PartialEq,
+    {
+        if self.contains(&v) {
+            false
+        } else {
+            self.0.push(v);
+            true
+        }
+    }
+
+    #[inline]
+    pub fn contains<Q>(&self, v: &Q) -> bool
+    where
+        TS::Des: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.0.iter().any(|e| e.borrow() == v)
+    }
+
+    /// Removes the element, shifting the following elements to preserve the order.
+    #[inline]
+    pub fn remove<Q>(&mut self, v: &Q) -> bool
+    where
+        TS::Des: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        match self.0.iter().position(|e| e.borrow() == v) {
+            Some(i) => {
+                self.0.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[TS::Des] {
+        &self.0
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<T, TS: BaseStrategy<T>> Default for SerdeSet<T, TS> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, TS: BaseStrategy<T>> Deref for SerdeSet<T, TS> {
+    type Target = [TS::Des];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T, TS: BaseStrategy<T>> IntoIterator for SerdeSet<T, TS> {
+    type Item = TS::Des;
+    type IntoIter = ::alloc::vec::IntoIter<Self::Item>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T, TS: BaseStrategy<T>> IntoIterator for &'a SerdeSet<T, TS> {
+    type Item = &'a TS::Des;
+    type IntoIter = ::core::slice::Iter<'a, TS::Des>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T, TS: BaseStrategy<T>> FromIterator<TS::Des> for SerdeSet<T, TS> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = TS::Des>>(iter: I) -> Self {
+        Self(iter.into_iter().collect(), PhantomData)
+    }
+}
+
+impl<T, TS: BaseStrategy<T>> From<Vec<TS::Des>> for SerdeSet<T, TS> {
+    #[inline]
+    fn from(data: Vec<TS::Des>) -> Self {
+        Self(data, PhantomData)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, TS: BaseStrategy<T>, S> From<HashSet<TS::Des, S>> for SerdeSet<T, TS> {
+    #[inline]
+    fn from(set: HashSet<TS::Des, S>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<T, TS: BaseStrategy<T>> From<BTreeSet<TS::Des>> for SerdeSet<T, TS> {
+    #[inline]
+    fn from(set: BTreeSet<TS::Des>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, TS: BaseStrategy<T>, S> From<SerdeSet<T, TS>> for HashSet<TS::Des, S>
+where
+    TS::Des: Eq + ::core::hash::Hash,
+    S: Default + ::core::hash::BuildHasher,
+{
+    #[inline]
+    fn from(v: SerdeSet<T, TS>) -> Self {
+        v.0.into_iter().collect()
+    }
+}
+
+impl<T, TS: BaseStrategy<T>> From<SerdeSet<T, TS>> for BTreeSet<TS::Des>
+where
+    TS::Des: Ord,
+{
+    #[inline]
+    fn from(v: SerdeSet<T, TS>) -> Self {
+        v.0.into_iter().collect()
+    }
+}
+
+impl<T, TS: SerStrategy<T> + Default> Serialize for SerdeSet<T, TS> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let human_readable = serializer.is_human_readable();
+        let ts = TS::default();
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for v in self {
+            seq.serialize_element(&ts.serialize_format(v, human_readable)?)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de, T, TS> Deserialize<'de> for SerdeSet<T, TS>
+where
+    T: Deserialize<'de>,
+    TS: DeStrategy<T> + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct SeqVisitor<T, TS> {
+            marker: PhantomData<(T, TS)>,
+            human_readable: bool,
+        }
+
+        impl<'de, T, TS> Visitor<'de> for SeqVisitor<T, TS>
+        where
+            T: Deserialize<'de>,
+            TS: DeStrategy<T> + Default,
+        {
+            type Value = SerdeSet<T, TS>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            #[inline]
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut values =
+                    SerdeSet::with_capacity(cautious_capacity::<TS::Des>(seq.size_hint()));
+                let ts = TS::default();
+
+                let mut index = 0;
+                while let Some(value) = seq.next_element()? {
+                    if let Some(v) = ts.deserialize_format(index, value, self.human_readable)? {
+                        values.insert_unchecked(v);
+                    }
+                    index += 1;
+                }
+
+                Ok(values)
+            }
+        }
+
+        let visitor = SeqVisitor {
+            marker: PhantomData,
+            human_readable: deserializer.is_human_readable(),
+        };
+        deserializer.deserialize_seq(visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IntKey;
+    use ::alloc::string::String;
+
+    #[test]
+    fn set_strategy() {
+        let mut set: SerdeSet<String, IntKey<i64>> = serde_json::from_str(r#"["3","1"]"#).unwrap();
+        assert!(set.contains(&1));
+        assert!(!set.insert(3));
+        assert!(set.insert(2));
+        assert_eq!(serde_json::to_string(&set).unwrap(), r#"["3","1","2"]"#);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::{BaseStrategy, DeStrategy, Entries, Linear, SerStrategy, SerdeMap};
+use ::core::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+use serde::{
+    de::{Deserialize, Deserializer},
+    ser::{Serialize, SerializeMap, Serializer},
+};
+use smallvec::SmallVec;
+
+/// Same as [`SerdeMap`], but based on `SmallVec`, which stores up to `N` entries inline,
+/// without allocating. There is no value strategy, and the key strategy isn't stored:
+/// `KS::default()` is used.
+#[derive(Debug, Clone)]
+pub struct SmallSerdeMap<K, V, KS: BaseStrategy<K> = Linear, const N: usize = 4>(
+    pub SmallVec<[(KS::Des, V); N]>,
+    PhantomData<KS>,
+);
+
+impl<K, V, KS: BaseStrategy<K>, const N: usize> SmallSerdeMap<K, V, KS, N> {
+    #[inline]
+    pub fn new() -> Self {
+        Self(SmallVec::new(), PhantomData)
+    }
+
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(SmallVec::with_capacity(capacity), PhantomData)
+    }
+
+    #[inline]
+    pub fn insert_unchecked(&mut self, k: KS::Des, v: V) {
+        self.0.push((k, v));
+    }
+
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[(KS::Des, V)] {
+        &self.0
+    }
+
+    #[inline(always)]
+    pub fn as_mut_slice(&mut self) -> &mut [(KS::Des, V)] {
+        &mut self.0
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if the entries are stored on the heap.
+    #[inline(always)]
+    pub fn spilled(&self) -> bool {
+        self.0.spilled()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, const N: usize> Default for SmallSerdeMap<K, V, KS, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, const N: usize> Deref for SmallSerdeMap<K, V, KS, N> {
+    type Target = [(KS::Des, V)];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, const N: usize> DerefMut for SmallSerdeMap<K, V, KS, N> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, const N: usize> IntoIterator for SmallSerdeMap<K, V, KS, N> {
+    type Item = (KS::Des, V);
+    type IntoIter = smallvec::IntoIter<[(KS::Des, V); N]>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, K, V, KS: BaseStrategy<K>, const N: usize> IntoIterator
+    for &'a SmallSerdeMap<K, V, KS, N>
+{
+    type Item = &'a (KS::Des, V);
+    type IntoIter = ::core::slice::Iter<'a, (KS::Des, V)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, const N: usize> FromIterator<(KS::Des, V)>
+    for SmallSerdeMap<K, V, KS, N>
+{
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (KS::Des, V)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect(), PhantomData)
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>, const N: usize> From<SerdeMap<K, V, KS, VS>>
+    for SmallSerdeMap<K, VS::Des, KS, N>
+{
+    #[inline]
+    fn from(map: SerdeMap<K, V, KS, VS>) -> Self {
+        Self(SmallVec::from_vec(map.0), PhantomData)
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K> + Default, VS: BaseStrategy<V> + Default, const N: usize>
+    From<SmallSerdeMap<K, VS::Des, KS, N>> for SerdeMap<K, V, KS, VS>
+{
+    #[inline]
+    fn from(map: SmallSerdeMap<K, VS::Des, KS, N>) -> Self {
+        map.0.into_vec().into()
+    }
+}
+
+impl<K, V: Serialize, KS: SerStrategy<K> + Default, const N: usize> Serialize
+    for SmallSerdeMap<K, V, KS, N>
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let human_readable = serializer.is_human_readable();
+        let ks = KS::default();
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (k, v) in self {
+            map.serialize_entry(&ks.serialize_format(k, human_readable)?, v)?;
+        }
+        map.end()
+    }
+}
+
+/// Entries are collected into a `Vec` first, which is moved inline if it fits into `N`.
+impl<'de, K, V, KS, const N: usize> Deserialize<'de> for SmallSerdeMap<K, V, KS, N>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    KS: DeStrategy<K> + Default,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let data = Entries::<K, V, KS>::deserialize(deserializer)?.0;
+        if data.len() <= N {
+            Ok(data.into_iter().collect())
+        } else {
+            Ok(Self(SmallVec::from_vec(data), PhantomData))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::alloc::{borrow::ToOwned, string::String, vec};
+
+    #[test]
+    fn round_trip() {
+        let json = r#"{"b":1,"a":2,"b":3}"#;
+        let map: SmallSerdeMap<String, u8> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            map.as_slice(),
+            [
+                ("b".to_owned(), 1),
+                ("a".to_owned(), 2),
+                ("b".to_owned(), 3)
+            ]
+        );
+        assert_eq!(serde_json::to_string(&map).unwrap(), json);
+
+        let map: SmallSerdeMap<String, u8> = serde_json::from_str(r#"[["a",1]]"#).unwrap();
+        assert_eq!(map.as_slice(), [("a".to_owned(), 1)]);
+        let map: SmallSerdeMap<String, u8> = serde_json::from_str("null").unwrap();
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn inline_capacity() {
+        let map: SmallSerdeMap<u8, u8, Linear, 2> =
+            serde_json::from_str(r#"{"1":1,"2":2}"#).unwrap();
+        assert_eq!(map.len(), 2);
+        assert!(!map.spilled());
+
+        let map: SmallSerdeMap<u8, u8, Linear, 2> = vec![(1, 1), (2, 2)].into_iter().collect();
+        assert!(!map.spilled());
+    }
+
+    #[test]
+    fn spill() {
+        let map: SmallSerdeMap<u8, u8, Linear, 2> =
+            serde_json::from_str(r#"{"1":1,"2":2,"3":3}"#).unwrap();
+        assert_eq!(map.as_slice(), [(1, 1), (2, 2), (3, 3)]);
+        assert!(map.spilled());
+
+        let back: SerdeMap<u8, u8> = map.into();
+        assert_eq!(back.0, vec![(1, 1), (2, 2), (3, 3)]);
+    }
+}
@@ -0,0 +1,295 @@
+use crate::{BaseStrategy, DeStrategy, Entries, Linear, SerStrategy, SerdeMap};
+use ::alloc::vec::Vec;
+use ::core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    marker::PhantomData,
+    mem,
+    ops::{Bound, Deref, RangeBounds},
+};
+use serde::{
+    de::{Deserialize, Deserializer},
+    ser::{Serialize, SerializeMap, Serializer},
+};
+
+/// `Map` based on `Vec`, which keeps its entries sorted by key.
+/// Lookups use binary search, and a key can be present only once.
+///
+/// (De)serialization works the same way as for [`SerdeMap`], using the same strategy.
+/// The strategy isn't stored, `KS::default()` is used, so it can't carry state.
+#[derive(Debug, Clone)]
+pub struct SortedSerdeMap<K, V, KS: BaseStrategy<K> = Linear>(Vec<(KS::Des, V)>, PhantomData<KS>);
+
+impl<K, V, KS: BaseStrategy<K>> SortedSerdeMap<K, V, KS> {
+    #[inline]
+    pub fn new() -> Self {
+        Self(Vec::new(), PhantomData)
+    }
+
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity), PhantomData)
+    }
+
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[(KS::Des, V)] {
+        &self.0
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> Vec<(KS::Des, V)> {
+        self.0
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>> SortedSerdeMap<K, V, KS>
+where
+    KS::Des: Ord,
+{
+    #[inline]
+    fn search<Q>(&self, k: &Q) -> Result<usize, usize>
+    where
+        KS::Des: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.0.binary_search_by(|(key, _)| key.borrow().cmp(k))
+    }
+
+    /// Inserts the entry at its sorted position.
+    /// If the key is already present, replaces the value and returns the old one.
+    #[inline]
+    pub fn insert(&mut self, k: KS::Des, v: V) -> Option<V> {
+        match self.search(&k) {
+            Ok(i) => Some(mem::replace(&mut self.0[i].1, v)),
+            Err(i) => {
+                self.0.insert(i, (k, v));
+                None
+            }
+        }
+    }
+
+    #[inline]
+    pub fn get<Q>(&self, k: &Q) -> Option<&V>
+    where
+        KS::Des: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.search(k).ok().map(|i| &self.0[i].1)
+    }
+
+    #[inline]
+    pub fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        KS::Des: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.search(k).ok().map(|i| &mut self.0[i].1)
+    }
+
+    #[inline]
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        KS::Des: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.search(k).is_ok()
+    }
+
+    #[inline]
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        KS::Des: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.search(k).ok().map(|i| self.0.remove(i).1)
+    }
+
+    /// Returns the entries with keys inside of the `range`.
+    #[inline]
+    pub fn range<Q, R>(&self, range: R) -> &[(KS::Des, V)]
+    where
+        KS::Des: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let point = |bound: Bound<&Q>, or: usize, inclusive_end: bool| match bound {
+            Bound::Included(q) => self
+                .0
+                .partition_point(|(key, _)| match key.borrow().cmp(q) {
+                    Ordering::Less => true,
+                    Ordering::Equal => inclusive_end,
+                    Ordering::Greater => false,
+                }),
+            Bound::Excluded(q) => self
+                .0
+                .partition_point(|(key, _)| match key.borrow().cmp(q) {
+                    Ordering::Less => true,
+                    Ordering::Equal => !inclusive_end,
+                    Ordering::Greater => false,
+                }),
+            Bound::Unbounded => or,
+        };
+        let start = point(range.start_bound(), 0, false);
+        let end = point(range.end_bound(), self.0.len(), true).max(start);
+        &self.0[start..end]
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>> Default for SortedSerdeMap<K, V, KS> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>> Deref for SortedSerdeMap<K, V, KS> {
+    type Target = [(KS::Des, V)];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>> IntoIterator for SortedSerdeMap<K, V, KS> {
+    type Item = (KS::Des, V);
+    type IntoIter = ::alloc::vec::IntoIter<Self::Item>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, K, V, KS: BaseStrategy<K>> IntoIterator for &'a SortedSerdeMap<K, V, KS> {
+    type Item = &'a (KS::Des, V);
+    type IntoIter = ::core::slice::Iter<'a, (KS::Des, V)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>> SortedSerdeMap<K, V, KS>
+where
+    KS::Des: Ord,
+{
+    /// Sorts the entries by key. For duplicate keys, the last value is kept.
+    fn from_unsorted(mut data: Vec<(KS::Des, V)>) -> Self {
+        data.sort_by(|a, b| a.0.cmp(&b.0));
+        // `a` is the later entry, so its value is moved into the retained one
+        data.dedup_by(|a, b| {
+            if a.0 == b.0 {
+                mem::swap(&mut a.1, &mut b.1);
+                true
+            } else {
+                false
+            }
+        });
+        Self(data, PhantomData)
+    }
+}
+
+/// Sorts the entries by key. For duplicate keys, the last value is kept.
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> From<SerdeMap<K, V, KS, VS>>
+    for SortedSerdeMap<K, VS::Des, KS>
+where
+    KS::Des: Ord,
+{
+    #[inline]
+    fn from(map: SerdeMap<K, V, KS, VS>) -> Self {
+        Self::from_unsorted(map.0)
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K> + Default, VS: BaseStrategy<V> + Default>
+    From<SortedSerdeMap<K, VS::Des, KS>> for SerdeMap<K, V, KS, VS>
+{
+    #[inline]
+    fn from(map: SortedSerdeMap<K, VS::Des, KS>) -> Self {
+        map.0.into()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>> FromIterator<(KS::Des, V)> for SortedSerdeMap<K, V, KS>
+where
+    KS::Des: Ord,
+{
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (KS::Des, V)>>(iter: T) -> Self {
+        Self::from_unsorted(iter.into_iter().collect())
+    }
+}
+
+impl<K, V: Serialize, KS: SerStrategy<K> + Default> Serialize for SortedSerdeMap<K, V, KS> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let human_readable = serializer.is_human_readable();
+        let ks = KS::default();
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (k, v) in self {
+            map.serialize_entry(&ks.serialize_format(k, human_readable)?, v)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, K, V, KS> Deserialize<'de> for SortedSerdeMap<K, V, KS>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    KS: DeStrategy<K> + Default,
+    KS::Des: Ord,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Entries::<K, V, KS>::deserialize(deserializer).map(|e| Self::from_unsorted(e.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::PublicKeys;
+    use ::alloc::{borrow::ToOwned, string::String, vec};
+
+    #[test]
+    fn strategy_hooks() {
+        let json = r#"{"b":1,"_a":2,"a":3}"#;
+        let map: SortedSerdeMap<String, u8, PublicKeys> = serde_json::from_str(json).unwrap();
+        assert_eq!(map.as_slice(), [("a".to_owned(), 3), ("b".to_owned(), 1)]);
+    }
+
+    #[test]
+    fn sorted_range() {
+        let mut map: SortedSerdeMap<u8, u8> =
+            vec![(5, 0), (1, 0), (3, 0), (1, 1)].into_iter().collect();
+        assert_eq!(map.as_slice(), &[(1, 1), (3, 0), (5, 0)]);
+        assert_eq!(map.insert(2, 0), None);
+        assert_eq!(map.insert(3, 1), Some(0));
+        assert_eq!(map.get(&3), Some(&1));
+        assert_eq!(map.range(2..5), &[(2, 0), (3, 1)]);
+        assert_eq!(map.range(2..=5).len(), 3);
+        assert_eq!(map.range((Bound::Excluded(1), Bound::Unbounded)).len(), 3);
+        assert!(map
+            .range((Bound::Included(4), Bound::Excluded(2)))
+            .is_empty());
+    }
+}
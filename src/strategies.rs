@@ -0,0 +1,1345 @@
+use crate::{cautious_capacity, BaseStrategy, DeStrategy, SerStrategy};
+#[cfg(feature = "intern")]
+use ::alloc::sync::Arc;
+use ::alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use ::core::{
+    borrow::Borrow,
+    fmt,
+    marker::PhantomData,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    str::FromStr,
+};
+#[cfg(feature = "intern")]
+use ::std::{
+    collections::HashSet,
+    sync::{Mutex, OnceLock},
+};
+use serde::{
+    de::{Deserialize, Deserializer, Error, SeqAccess, Visitor},
+    ser::{self, Serialize, Serializer},
+};
+
+/// Implements the traits for a strategy marker without bounds on its parameters
+macro_rules! marker {
+    ($name:ident<$($t:ident),+>) => {
+        impl<$($t),+> Default for $name<$($t),+> {
+            #[inline(always)]
+            fn default() -> Self {
+                Self(PhantomData)
+            }
+        }
+
+        impl<$($t),+> Clone for $name<$($t),+> {
+            #[inline(always)]
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<$($t),+> Copy for $name<$($t),+> {}
+
+        impl<$($t),+> fmt::Debug for $name<$($t),+> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(stringify!($name))
+            }
+        }
+    };
+}
+
+/// Stores `T`, serialized as `String` with `Display` and parsed back with `FromStr`.
+/// Exact for types with lossless string forms, e.g. `DisplayFromStr<rust_decimal::Decimal>`
+/// keeps price levels as exact decimal strings without a float round trip.
+pub struct DisplayFromStr<T>(PhantomData<T>);
+
+marker!(DisplayFromStr<T>);
+
+/// Stores the integer `T` (`i8..i128` or `u8..u128`), serialized as a string,
+/// which JSON requires for keys. Invalid and overflowing keys are reported with the key.
+pub struct IntKey<T>(PhantomData<T>);
+
+marker!(IntKey<T>);
+
+macro_rules! int_key {
+    ($($t:ty),*) => {$(
+        impl BaseStrategy<String> for IntKey<$t> {
+            type Des = $t;
+        }
+
+        impl SerStrategy<String> for IntKey<$t> {
+            type SerRet<'s> = String;
+
+            #[inline]
+            fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+                d.to_string()
+            }
+        }
+
+        impl DeStrategy<String> for IntKey<$t> {
+            #[inline]
+            fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+                s.parse().map_err(|e| {
+                    E::custom(format_args!(concat!("invalid ", stringify!($t), " key {:?}: {}"), s, e))
+                })
+            }
+        }
+    )*};
+}
+
+int_key!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Stores `uuid::Uuid`, serialized as a string in the format `F`
+/// (`uuid::fmt::Hyphenated`, `Simple` or `Urn`), any of them is parsed back
+#[cfg(feature = "uuid")]
+pub struct UuidKey<F = ::uuid::fmt::Hyphenated>(PhantomData<F>);
+
+#[cfg(feature = "uuid")]
+marker!(UuidKey<F>);
+
+#[cfg(feature = "uuid")]
+impl<F> BaseStrategy<String> for UuidKey<F> {
+    type Des = ::uuid::Uuid;
+}
+
+#[cfg(feature = "uuid")]
+impl<F: From<::uuid::Uuid> + fmt::Display> SerStrategy<String> for UuidKey<F> {
+    type SerRet<'s> = String;
+
+    #[inline]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        F::from(*d).to_string()
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<F> DeStrategy<String> for UuidKey<F> {
+    #[inline]
+    fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+        ::uuid::Uuid::parse_str(&s).map_err(Error::custom)
+    }
+}
+
+impl<T> BaseStrategy<String> for DisplayFromStr<T> {
+    type Des = T;
+}
+
+impl<T: fmt::Display> SerStrategy<String> for DisplayFromStr<T> {
+    type SerRet<'s> = String;
+
+    #[inline]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        d.to_string()
+    }
+}
+
+impl<T: FromStr> DeStrategy<String> for DisplayFromStr<T>
+where
+    T::Err: fmt::Display,
+{
+    #[inline]
+    fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+        s.parse().map_err(Error::custom)
+    }
+}
+
+/// Stores `chrono::DateTime<Utc>`, serialized as an RFC 3339 string (`2024-01-02T03:04:05Z`)
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rfc3339Key;
+
+#[cfg(feature = "chrono")]
+impl BaseStrategy<String> for Rfc3339Key {
+    type Des = ::chrono::DateTime<::chrono::Utc>;
+}
+
+#[cfg(feature = "chrono")]
+impl SerStrategy<String> for Rfc3339Key {
+    type SerRet<'s> = String;
+
+    #[inline]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        d.to_rfc3339_opts(::chrono::SecondsFormat::AutoSi, true)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl DeStrategy<String> for Rfc3339Key {
+    #[inline]
+    fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+        ::chrono::DateTime::parse_from_rfc3339(&s)
+            .map(|d| d.to_utc())
+            .map_err(|e| E::custom(format_args!("invalid RFC 3339 key {s:?}: {e}")))
+    }
+}
+
+/// Stores `chrono::DateTime<Utc>`, serialized as a string of milliseconds since the Unix epoch
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnixMillisKey;
+
+#[cfg(feature = "chrono")]
+impl BaseStrategy<String> for UnixMillisKey {
+    type Des = ::chrono::DateTime<::chrono::Utc>;
+}
+
+#[cfg(feature = "chrono")]
+impl SerStrategy<String> for UnixMillisKey {
+    type SerRet<'s> = String;
+
+    #[inline]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        d.timestamp_millis().to_string()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl DeStrategy<String> for UnixMillisKey {
+    #[inline]
+    fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+        s.parse()
+            .ok()
+            .and_then(::chrono::DateTime::from_timestamp_millis)
+            .ok_or_else(|| E::custom(format_args!("invalid Unix millis key {s:?}")))
+    }
+}
+
+/// Letter case of the digits, which [`Hex`] serializes. Both cases are parsed.
+pub trait HexCase {
+    const DIGITS: &'static [u8; 16];
+}
+
+/// `0123456789abcdef`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HexLower;
+
+impl HexCase for HexLower {
+    const DIGITS: &'static [u8; 16] = b"0123456789abcdef";
+}
+
+/// `0123456789ABCDEF`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HexUpper;
+
+impl HexCase for HexUpper {
+    const DIGITS: &'static [u8; 16] = b"0123456789ABCDEF";
+}
+
+/// Stores bytes `D` (`Vec<u8>` or `[u8; N]`), serialized as a hex string in the case `C`
+pub struct Hex<D = Vec<u8>, C = HexLower>(PhantomData<(D, C)>);
+
+marker!(Hex<D, C>);
+
+fn decode_hex<E: Error>(s: &str) -> Result<Vec<u8>, E> {
+    #[inline]
+    fn digit(c: u8) -> Option<u8> {
+        (c as char).to_digit(16).map(|d| d as u8)
+    }
+
+    if !s.len().is_multiple_of(2) {
+        return Err(E::custom(format_args!("odd length of hex key {s:?}")));
+    }
+    s.as_bytes()
+        .chunks_exact(2)
+        .map(|pair| Some(digit(pair[0])? << 4 | digit(pair[1])?))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| E::custom(format_args!("invalid hex key {s:?}")))
+}
+
+impl<D: AsRef<[u8]>, C> BaseStrategy<String> for Hex<D, C> {
+    type Des = D;
+}
+
+impl<D: AsRef<[u8]>, C: HexCase> SerStrategy<String> for Hex<D, C> {
+    type SerRet<'s> = String;
+
+    #[inline]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        let bytes = d.as_ref();
+        let mut s = String::with_capacity(bytes.len() * 2);
+        for b in bytes {
+            s.push(C::DIGITS[(b >> 4) as usize] as char);
+            s.push(C::DIGITS[(b & 0xf) as usize] as char);
+        }
+        s
+    }
+}
+
+impl<C> DeStrategy<String> for Hex<Vec<u8>, C> {
+    #[inline]
+    fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+        decode_hex(&s)
+    }
+}
+
+impl<C, const N: usize> DeStrategy<String> for Hex<[u8; N], C> {
+    #[inline]
+    fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+        let bytes = decode_hex::<E>(&s)?;
+        let len = bytes.len();
+        bytes
+            .try_into()
+            .map_err(|_| E::custom(format_args!("expected {N} bytes in hex key, got {len}")))
+    }
+}
+
+/// Stores the network address `T` (`IpAddr`, `Ipv4Addr`, `Ipv6Addr`, `SocketAddr`,
+/// `SocketAddrV4` or `SocketAddrV6`), serialized as a string.
+/// Invalid keys are reported with the key.
+pub struct AddrKey<T = IpAddr>(PhantomData<T>);
+
+marker!(AddrKey<T>);
+
+macro_rules! addr_key {
+    ($($t:ty),*) => {$(
+        impl BaseStrategy<String> for AddrKey<$t> {
+            type Des = $t;
+        }
+
+        impl SerStrategy<String> for AddrKey<$t> {
+            type SerRet<'s> = String;
+
+            #[inline]
+            fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+                d.to_string()
+            }
+        }
+
+        impl DeStrategy<String> for AddrKey<$t> {
+            #[inline]
+            fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+                s.parse()
+                    .map_err(|e| E::custom(format_args!("invalid address key {s:?}: {e}")))
+            }
+        }
+    )*};
+}
+
+addr_key!(
+    IpAddr,
+    Ipv4Addr,
+    Ipv6Addr,
+    SocketAddr,
+    SocketAddrV4,
+    SocketAddrV6
+);
+
+/// Stores `http::HeaderName`, so the keys are compared case-insensitively
+/// (they are parsed to lowercase), serialized as the lowercase name
+#[cfg(feature = "http")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeaderKey;
+
+#[cfg(feature = "http")]
+impl BaseStrategy<String> for HeaderKey {
+    type Des = ::http::HeaderName;
+}
+
+#[cfg(feature = "http")]
+impl SerStrategy<String> for HeaderKey {
+    type SerRet<'s> = &'s str;
+
+    #[inline]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        d.as_str()
+    }
+}
+
+#[cfg(feature = "http")]
+impl DeStrategy<String> for HeaderKey {
+    #[inline]
+    fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+        ::http::HeaderName::from_bytes(s.as_bytes()).map_err(Error::custom)
+    }
+}
+
+/// Case of string keys, applied by [`CaseKey`]
+pub trait KeyCase {
+    fn convert(s: &str) -> String;
+}
+
+/// Keeps the key unchanged
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AsIs;
+
+impl KeyCase for AsIs {
+    #[inline(always)]
+    fn convert(s: &str) -> String {
+        s.into()
+    }
+}
+
+/// `somekey`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Lowercase;
+
+impl KeyCase for Lowercase {
+    #[inline]
+    fn convert(s: &str) -> String {
+        s.to_lowercase()
+    }
+}
+
+/// `some_key`, words are split on `_`, `-`, spaces and case changes (`HTTPServer` is `http_server`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SnakeCase;
+
+impl KeyCase for SnakeCase {
+    fn convert(s: &str) -> String {
+        let mut out = String::with_capacity(s.len() + 4);
+        let mut chars = s.chars().peekable();
+        let mut prev: Option<char> = None;
+        while let Some(c) = chars.next() {
+            if matches!(c, '_' | '-' | ' ') {
+                if !out.is_empty() && !out.ends_with('_') {
+                    out.push('_');
+                }
+            } else if c.is_uppercase() {
+                let next_lower = chars.peek().is_some_and(|n| n.is_lowercase());
+                let boundary = prev.is_some_and(|p| {
+                    p.is_lowercase() || p.is_ascii_digit() || (p.is_uppercase() && next_lower)
+                });
+                if boundary && !out.ends_with('_') {
+                    out.push('_');
+                }
+                out.extend(c.to_lowercase());
+            } else {
+                out.push(c);
+            }
+            prev = Some(c);
+        }
+        if out.ends_with('_') {
+            out.pop();
+        }
+        out
+    }
+}
+
+/// `someKey`, words are split the same way as in [`SnakeCase`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CamelCase;
+
+impl KeyCase for CamelCase {
+    fn convert(s: &str) -> String {
+        let snake = SnakeCase::convert(s);
+        let mut out = String::with_capacity(snake.len());
+        for (i, word) in snake.split('_').filter(|w| !w.is_empty()).enumerate() {
+            let mut chars = word.chars();
+            if i > 0 {
+                out.extend(chars.next().into_iter().flat_map(char::to_uppercase));
+            }
+            out.push_str(chars.as_str());
+        }
+        out
+    }
+}
+
+/// Stores string keys in the case `S`, and serializes them in the case `E`:
+/// `CaseKey<SnakeCase, CamelCase>` stores `some_key` and emits `someKey`
+pub struct CaseKey<S, E = AsIs>(PhantomData<(S, E)>);
+
+marker!(CaseKey<S, E>);
+
+impl<S, E> BaseStrategy<String> for CaseKey<S, E> {
+    type Des = String;
+}
+
+impl<S, E: KeyCase> SerStrategy<String> for CaseKey<S, E> {
+    type SerRet<'s> = String;
+
+    #[inline]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        E::convert(d)
+    }
+}
+
+impl<S: KeyCase, E> DeStrategy<String> for CaseKey<S, E> {
+    #[inline]
+    fn deserialize<Er: Error>(s: String) -> Result<Self::Des, Er> {
+        Ok(S::convert(&s))
+    }
+}
+
+/// Trims surrounding whitespace of strings during deserialization,
+/// so `" key "` and `"key"` become the same key. Serialized unchanged.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Trim;
+
+impl BaseStrategy<String> for Trim {
+    type Des = String;
+}
+
+impl SerStrategy<String> for Trim {
+    type SerRet<'s> = &'s str;
+
+    #[inline(always)]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        d
+    }
+}
+
+impl DeStrategy<String> for Trim {
+    #[inline]
+    fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+        let trimmed = s.trim();
+        if trimmed.len() == s.len() {
+            Ok(s)
+        } else {
+            Ok(trimmed.into())
+        }
+    }
+}
+
+/// Handles keys, which [`EnumKey`] failed to parse
+pub trait UnknownKey<T> {
+    fn unknown<E: Error>(s: String) -> Result<T, E>;
+}
+
+/// Fails the deserialization on an unknown key
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DenyUnknown;
+
+impl<T> UnknownKey<T> for DenyUnknown {
+    #[inline]
+    fn unknown<E: Error>(s: String) -> Result<T, E> {
+        Err(E::custom(format_args!("unknown key {s:?}")))
+    }
+}
+
+/// Maps an unknown key to `T::default()`, which is usually a catch-all variant
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultUnknown;
+
+impl<T: Default> UnknownKey<T> for DefaultUnknown {
+    #[inline(always)]
+    fn unknown<E: Error>(_: String) -> Result<T, E> {
+        Ok(T::default())
+    }
+}
+
+/// Stores the enum `T` (or any `FromStr + AsRef<str>` type), serialized as its string name.
+/// Works with `strum`'s `EnumString` and `AsRefStr` derives.
+/// Unknown keys are handled by `U`: [`DenyUnknown`] or [`DefaultUnknown`].
+pub struct EnumKey<T, U = DenyUnknown>(PhantomData<(T, U)>);
+
+marker!(EnumKey<T, U>);
+
+impl<T, U> BaseStrategy<String> for EnumKey<T, U> {
+    type Des = T;
+}
+
+impl<T: AsRef<str>, U> SerStrategy<String> for EnumKey<T, U> {
+    type SerRet<'s> = &'s str;
+
+    #[inline(always)]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        d.as_ref()
+    }
+}
+
+impl<T: FromStr, U: UnknownKey<T>> DeStrategy<String> for EnumKey<T, U> {
+    #[inline]
+    fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+        match s.parse() {
+            Ok(d) => Ok(d),
+            Err(_) => U::unknown(s),
+        }
+    }
+}
+
+/// Feeds the stored value of `A` into `B`: `Ser -> A::Des -> B::Des`,
+/// e.g. `Chain<Trim, DisplayFromStr<i64>>` parses trimmed keys.
+/// Serialization goes back through `B` and then `A`, so the serialized form of `B`
+/// has to borrow as `A::Des`.
+pub struct Chain<A, B>(PhantomData<(A, B)>);
+
+marker!(Chain<A, B>);
+
+impl<Ser, A: BaseStrategy<Ser>, B: BaseStrategy<A::Des>> BaseStrategy<Ser> for Chain<A, B> {
+    type Des = B::Des;
+}
+
+/// Serialized form of [`Chain`]
+pub struct ChainRet<'s, Ser, A, B>(B::SerRet<'s>, PhantomData<(&'s Ser, A)>)
+where
+    A: BaseStrategy<Ser>,
+    A::Des: 's,
+    B: SerStrategy<A::Des>;
+
+impl<'s, Ser, A, B> Serialize for ChainRet<'s, Ser, A, B>
+where
+    A: SerStrategy<Ser>,
+    A::Des: 's,
+    B: SerStrategy<A::Des>,
+    B::SerRet<'s>: Borrow<A::Des>,
+{
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        A::try_serialize(self.0.borrow())?.serialize(serializer)
+    }
+}
+
+impl<Ser, A, B> SerStrategy<Ser> for Chain<A, B>
+where
+    A: SerStrategy<Ser>,
+    A::Des: 'static,
+    B: SerStrategy<A::Des>,
+    for<'s> B::SerRet<'s>: Borrow<A::Des>,
+{
+    type SerRet<'s>
+        = ChainRet<'s, Ser, A, B>
+    where
+        Ser: 's;
+
+    #[inline]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        ChainRet(B::serialize(d), PhantomData)
+    }
+
+    #[inline]
+    fn try_serialize<E: ser::Error>(d: &Self::Des) -> Result<Self::SerRet<'_>, E> {
+        B::try_serialize(d).map(|ret| ChainRet(ret, PhantomData))
+    }
+}
+
+impl<Ser, A: DeStrategy<Ser>, B: DeStrategy<A::Des>> DeStrategy<Ser> for Chain<A, B> {
+    #[inline]
+    fn deserialize<E: Error>(s: Ser) -> Result<Self::Des, E> {
+        B::deserialize(A::deserialize(s)?)
+    }
+}
+
+/// Conversion of the stored type `D`, applied by [`MapWith`]
+pub trait Convert<D> {
+    type Target;
+
+    /// Called after deserialization
+    fn convert<E: Error>(d: D) -> Result<Self::Target, E>;
+
+    /// Called before serialization
+    fn revert(target: &Self::Target) -> D;
+}
+
+/// Applies the [`Convert`] functions `F` on top of the strategy `S`: `Ser -> S::Des -> F::Target`
+pub struct MapWith<S, F>(PhantomData<(S, F)>);
+
+marker!(MapWith<S, F>);
+
+impl<Ser, S: BaseStrategy<Ser>, F: Convert<S::Des>> BaseStrategy<Ser> for MapWith<S, F> {
+    type Des = F::Target;
+}
+
+/// Serialized form of [`MapWith`]
+pub struct MapWithRet<'s, Ser, S: BaseStrategy<Ser>>(S::Des, PhantomData<&'s Ser>);
+
+impl<Ser, S: SerStrategy<Ser>> Serialize for MapWithRet<'_, Ser, S> {
+    #[inline]
+    fn serialize<Sr: Serializer>(&self, serializer: Sr) -> Result<Sr::Ok, Sr::Error> {
+        S::try_serialize(&self.0)?.serialize(serializer)
+    }
+}
+
+impl<Ser, S: SerStrategy<Ser>, F: Convert<S::Des>> SerStrategy<Ser> for MapWith<S, F> {
+    type SerRet<'s>
+        = MapWithRet<'s, Ser, S>
+    where
+        Ser: 's;
+
+    #[inline]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        MapWithRet(F::revert(d), PhantomData)
+    }
+}
+
+impl<Ser, S: DeStrategy<Ser>, F: Convert<S::Des>> DeStrategy<Ser> for MapWith<S, F> {
+    #[inline]
+    fn deserialize<E: Error>(s: Ser) -> Result<Self::Des, E> {
+        F::convert(S::deserialize(s)?)
+    }
+}
+
+/// Check of a deserialized value, applied by [`Validated`]
+pub trait Rule<D> {
+    fn check<E: Error>(d: &D) -> Result<(), E>;
+}
+
+/// Rejects strings longer than `N` chars
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaxLen<const N: usize>;
+
+impl<D: AsRef<str>, const N: usize> Rule<D> for MaxLen<N> {
+    #[inline]
+    fn check<E: Error>(d: &D) -> Result<(), E> {
+        let len = d.as_ref().chars().count();
+        if len > N {
+            return Err(E::custom(format_args!(
+                "{:?} is too long: {len} chars, the limit is {N}",
+                d.as_ref()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Runs the [`Rule`] `R` after `S::deserialize`, rejecting invalid entries.
+/// Serialized the same way as `S`.
+pub struct Validated<S, R>(PhantomData<(S, R)>);
+
+marker!(Validated<S, R>);
+
+impl<Ser, S: BaseStrategy<Ser>, R> BaseStrategy<Ser> for Validated<S, R> {
+    type Des = S::Des;
+}
+
+impl<Ser, S: SerStrategy<Ser>, R> SerStrategy<Ser> for Validated<S, R> {
+    type SerRet<'s>
+        = S::SerRet<'s>
+    where
+        Ser: 's;
+
+    #[inline(always)]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        S::serialize(d)
+    }
+
+    #[inline(always)]
+    fn try_serialize<E: ser::Error>(d: &Self::Des) -> Result<Self::SerRet<'_>, E> {
+        S::try_serialize(d)
+    }
+}
+
+impl<Ser, S: DeStrategy<Ser>, R: Rule<S::Des>> DeStrategy<Ser> for Validated<S, R> {
+    #[inline]
+    fn deserialize<E: Error>(s: Ser) -> Result<Self::Des, E> {
+        let d = S::deserialize(s)?;
+        R::check(&d)?;
+        Ok(d)
+    }
+}
+
+/// Stores string keys as `T` (e.g. `CompactString` or `SmartString`) converted from `String`,
+/// serialized through `AsRef<str>`. Lookups work through `T: Borrow<str>`: `map.get("key")`.
+pub struct FromString<T>(PhantomData<T>);
+
+marker!(FromString<T>);
+
+impl<T> BaseStrategy<String> for FromString<T> {
+    type Des = T;
+}
+
+impl<T: AsRef<str>> SerStrategy<String> for FromString<T> {
+    type SerRet<'s> = &'s str;
+
+    #[inline(always)]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        d.as_ref()
+    }
+}
+
+impl<T: From<String>> DeStrategy<String> for FromString<T> {
+    #[inline(always)]
+    fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+        Ok(s.into())
+    }
+}
+
+/// Shared set of strings, used by [`Interned`]. Clones share the same storage.
+/// A string stays stored until [`clear`](Self::clear) or [`evict_unused`](Self::evict_unused)
+/// is called, even if no map uses it, so long running processes should scope or evict them.
+#[cfg(feature = "intern")]
+#[derive(Debug, Clone, Default)]
+pub struct Interner(Arc<Mutex<HashSet<Arc<str>>>>);
+
+#[cfg(feature = "intern")]
+impl Interner {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Process-wide interner, used by [`Interned::default()`].
+    /// It lives until the process exits, so its strings are only freed by the eviction methods.
+    pub fn global() -> &'static Self {
+        static GLOBAL: OnceLock<Interner> = OnceLock::new();
+        GLOBAL.get_or_init(Self::new)
+    }
+
+    /// Returns the stored copy of `s`, storing it first if needed
+    pub fn intern(&self, s: &str) -> Arc<str> {
+        let mut set = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        match set.get(s) {
+            Some(interned) => interned.clone(),
+            None => {
+                let interned: Arc<str> = s.into();
+                set.insert(interned.clone());
+                interned
+            }
+        }
+    }
+
+    /// Removes all strings. The ones already in use stay valid, but aren't shared anymore
+    pub fn clear(&self) {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// Removes the strings which are only referenced by the interner
+    pub fn evict_unused(&self) {
+        let mut set = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        set.retain(|s| Arc::strong_count(s) > 1);
+    }
+
+    /// Number of distinct strings
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Stores string keys as `Arc<str>` from an [`Interner`], so repeated keys across maps share
+/// a single allocation. The default instance uses [`Interner::global()`],
+/// pass another one through [`SerdeMapSeed`](crate::SerdeMapSeed) to scope it.
+#[cfg(feature = "intern")]
+#[derive(Debug, Clone)]
+pub struct Interned(pub Interner);
+
+#[cfg(feature = "intern")]
+impl Default for Interned {
+    #[inline]
+    fn default() -> Self {
+        Self(Interner::global().clone())
+    }
+}
+
+#[cfg(feature = "intern")]
+impl BaseStrategy<String> for Interned {
+    type Des = Arc<str>;
+}
+
+#[cfg(feature = "intern")]
+impl SerStrategy<String> for Interned {
+    type SerRet<'s> = &'s str;
+
+    #[inline(always)]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        d
+    }
+}
+
+#[cfg(feature = "intern")]
+impl DeStrategy<String> for Interned {
+    #[inline]
+    fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+        Ok(Interner::global().intern(&s))
+    }
+
+    #[inline]
+    fn deserialize_with<E: Error>(&self, s: String) -> Result<Self::Des, E> {
+        Ok(self.0.intern(&s))
+    }
+}
+
+/// Owned bytes, (de)serialized as a byte string (`serialize_bytes`/`deserialize_byte_buf`),
+/// so binary formats don't emit a sequence of integers. Used as `Ser` of [`BytesKey`].
+/// Also accepts sequences of integers and strings, because some formats emit those.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteBuf(pub Vec<u8>);
+
+impl Serialize for ByteBuf {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ByteBufVisitor;
+
+        impl<'de> Visitor<'de> for ByteBufVisitor {
+            type Value = ByteBuf;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a byte string")
+            }
+
+            #[inline]
+            fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(ByteBuf(v.into()))
+            }
+
+            #[inline]
+            fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(ByteBuf(v))
+            }
+
+            #[inline]
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(ByteBuf(v.as_bytes().into()))
+            }
+
+            #[inline]
+            fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+                Ok(ByteBuf(v.into_bytes()))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut bytes = Vec::with_capacity(cautious_capacity::<u8>(seq.size_hint()));
+                while let Some(b) = seq.next_element()? {
+                    bytes.push(b);
+                }
+                Ok(ByteBuf(bytes))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(ByteBufVisitor)
+    }
+}
+
+/// Borrowed bytes, serialized as a byte string. Serialized form of [`BytesKey`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawBytes<'a>(pub &'a [u8]);
+
+impl Serialize for RawBytes<'_> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// Stores bytes `D` (`Vec<u8>` or `[u8; N]`), (de)serialized as a byte string through [`ByteBuf`]:
+/// `SerdeMap<ByteBuf, V, BytesKey<[u8; 32]>>`
+pub struct BytesKey<D = Vec<u8>>(PhantomData<D>);
+
+marker!(BytesKey<D>);
+
+impl<D: AsRef<[u8]>> BaseStrategy<ByteBuf> for BytesKey<D> {
+    type Des = D;
+}
+
+impl<D: AsRef<[u8]>> SerStrategy<ByteBuf> for BytesKey<D> {
+    type SerRet<'s> = RawBytes<'s>;
+
+    #[inline(always)]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        RawBytes(d.as_ref())
+    }
+}
+
+impl DeStrategy<ByteBuf> for BytesKey<Vec<u8>> {
+    #[inline(always)]
+    fn deserialize<E: Error>(s: ByteBuf) -> Result<Self::Des, E> {
+        Ok(s.0)
+    }
+}
+
+impl<const N: usize> DeStrategy<ByteBuf> for BytesKey<[u8; N]> {
+    #[inline]
+    fn deserialize<E: Error>(s: ByteBuf) -> Result<Self::Des, E> {
+        let len = s.0.len();
+        s.0.try_into()
+            .map_err(|_| E::custom(format_args!("expected {N} bytes, got {len}")))
+    }
+}
+
+/// Alphabet and padding of [`Base64Key`]
+#[cfg(feature = "base64")]
+pub trait Base64Engine {
+    const ENGINE: ::base64::engine::GeneralPurpose;
+}
+
+#[cfg(feature = "base64")]
+macro_rules! base64_engine {
+    ($($(#[$meta:meta])* $name:ident => $engine:ident,)*) => {$(
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct $name;
+
+        impl Base64Engine for $name {
+            const ENGINE: ::base64::engine::GeneralPurpose = ::base64::engine::general_purpose::$engine;
+        }
+    )*};
+}
+
+#[cfg(feature = "base64")]
+base64_engine! {
+    /// Standard alphabet with padding
+    Base64Standard => STANDARD,
+    /// Standard alphabet without padding
+    Base64StandardNoPad => STANDARD_NO_PAD,
+    /// URL-safe alphabet with padding
+    Base64UrlSafe => URL_SAFE,
+    /// URL-safe alphabet without padding
+    Base64UrlSafeNoPad => URL_SAFE_NO_PAD,
+}
+
+/// Stores bytes `D` (`Vec<u8>` or `[u8; N]`), serialized as a base64 string
+/// with the alphabet and padding of `A`
+#[cfg(feature = "base64")]
+pub struct Base64Key<A = Base64Standard, D = Vec<u8>>(PhantomData<(A, D)>);
+
+#[cfg(feature = "base64")]
+marker!(Base64Key<A, D>);
+
+#[cfg(feature = "base64")]
+impl<A, D: AsRef<[u8]>> BaseStrategy<String> for Base64Key<A, D> {
+    type Des = D;
+}
+
+#[cfg(feature = "base64")]
+impl<A: Base64Engine, D: AsRef<[u8]>> SerStrategy<String> for Base64Key<A, D> {
+    type SerRet<'s> = String;
+
+    #[inline]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        ::base64::Engine::encode(&A::ENGINE, d)
+    }
+}
+
+#[cfg(feature = "base64")]
+impl<A: Base64Engine> DeStrategy<String> for Base64Key<A, Vec<u8>> {
+    #[inline]
+    fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+        ::base64::Engine::decode(&A::ENGINE, &s)
+            .map_err(|e| E::custom(format_args!("invalid base64 key {s:?}: {e}")))
+    }
+}
+
+#[cfg(feature = "base64")]
+impl<A: Base64Engine, const N: usize> DeStrategy<String> for Base64Key<A, [u8; N]> {
+    #[inline]
+    fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+        let bytes = Base64Key::<A>::deserialize::<E>(s)?;
+        let len = bytes.len();
+        bytes
+            .try_into()
+            .map_err(|_| E::custom(format_args!("expected {N} bytes in base64 key, got {len}")))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Linear, SerdeMap};
+    use ::alloc::vec;
+
+    #[test]
+    fn display_from_str() {
+        let json = r#"{"1":"a","-2":"b"}"#;
+        let map: SerdeMap<String, String, DisplayFromStr<i64>> =
+            serde_json::from_str(json).unwrap();
+        assert_eq!(map.0, vec![(1, "a".to_string()), (-2, "b".to_string())]);
+        assert_eq!(serde_json::to_string(&map).unwrap(), json);
+        assert!(
+            serde_json::from_str::<SerdeMap<String, u8, DisplayFromStr<i64>>>(r#"{"x":1}"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn int_key() {
+        let json = r#"{"255":1,"0":2}"#;
+        let map: SerdeMap<String, u8, IntKey<u8>> = serde_json::from_str(json).unwrap();
+        assert_eq!(map.0, vec![(255, 1), (0, 2)]);
+        assert_eq!(serde_json::to_string(&map).unwrap(), json);
+
+        let err = serde_json::from_str::<SerdeMap<String, u8, IntKey<u8>>>(r#"{"256":1}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with(r#"entry 0: invalid u8 key "256""#), "{err}");
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn uuid_key() {
+        let json = r#"{"67e55044-10b1-426f-9247-bb680e5fe0c8":1}"#;
+        let map: SerdeMap<String, u8, UuidKey> = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&map).unwrap(), json);
+
+        let map: SerdeMap<String, u8, UuidKey<::uuid::fmt::Simple>> = SerdeMap::from_iter(map.0);
+        assert_eq!(
+            serde_json::to_string(&map).unwrap(),
+            r#"{"67e5504410b1426f9247bb680e5fe0c8":1}"#
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_keys() {
+        let json = r#"{"2024-01-02T03:04:05.678Z":1}"#;
+        let map: SerdeMap<String, u8, Rfc3339Key> = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&map).unwrap(), json);
+        let offset: SerdeMap<String, u8, Rfc3339Key> =
+            serde_json::from_str(r#"{"2024-01-02T05:04:05.678+02:00":1}"#).unwrap();
+        assert_eq!(offset.0, map.0);
+
+        let map: SerdeMap<String, u8, UnixMillisKey> = SerdeMap::from_iter(map.0);
+        assert_eq!(
+            serde_json::to_string(&map).unwrap(),
+            r#"{"1704164645678":1}"#
+        );
+        let err = serde_json::from_str::<SerdeMap<String, u8, UnixMillisKey>>(r#"{"x":1}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with(r#"entry 0: invalid Unix millis key "x""#),
+            "{err}"
+        );
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn base64_key() {
+        let json = r#"{"AQID":1}"#;
+        let map: SerdeMap<String, u8, Base64Key> = serde_json::from_str(json).unwrap();
+        assert_eq!(map.0, vec![(vec![1, 2, 3], 1)]);
+        assert_eq!(serde_json::to_string(&map).unwrap(), json);
+
+        let map: SerdeMap<String, u8, Base64Key<Base64UrlSafeNoPad, [u8; 2]>> =
+            serde_json::from_str(r#"{"__8":1}"#).unwrap();
+        assert_eq!(map.0, vec![([255, 255], 1)]);
+        let err =
+            serde_json::from_str::<SerdeMap<String, u8, Base64Key<Base64Standard, [u8; 2]>>>(json)
+                .unwrap_err()
+                .to_string();
+        assert!(
+            err.starts_with("entry 0: expected 2 bytes in base64 key, got 3"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn hex() {
+        let json = r#"{"00ff":1}"#;
+        let map: SerdeMap<String, u8, Hex<[u8; 2]>> = serde_json::from_str(json).unwrap();
+        assert_eq!(map.0, vec![([0, 255], 1)]);
+        assert_eq!(serde_json::to_string(&map).unwrap(), json);
+
+        let map: SerdeMap<String, u8, Hex<Vec<u8>, HexUpper>> =
+            serde_json::from_str(r#"{"0aFf":1}"#).unwrap();
+        assert_eq!(map.0, vec![(vec![10, 255], 1)]);
+        assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"0AFF":1}"#);
+        for json in [r#"{"0":1}"#, r#"{"0x":1}"#] {
+            assert!(serde_json::from_str::<SerdeMap<String, u8, Hex>>(json).is_err());
+        }
+    }
+
+    #[test]
+    fn case_key() {
+        for (s, snake, camel) in [
+            ("someKey", "some_key", "someKey"),
+            ("some_key", "some_key", "someKey"),
+            ("HTTPServer2Go", "http_server2_go", "httpServer2Go"),
+            ("-a b-", "a_b", "aB"),
+        ] {
+            assert_eq!(SnakeCase::convert(s), snake);
+            assert_eq!(CamelCase::convert(s), camel);
+        }
+
+        let json = r#"{"userId":1,"USER_NAME":2}"#;
+        let map: SerdeMap<String, u8, CaseKey<SnakeCase, CamelCase>> =
+            serde_json::from_str(json).unwrap();
+        assert_eq!(map.0, vec![("user_id".into(), 1), ("user_name".into(), 2)]);
+        assert_eq!(
+            serde_json::to_string(&map).unwrap(),
+            r#"{"userId":1,"userName":2}"#
+        );
+    }
+
+    #[test]
+    fn trim() {
+        let json = r#"{" key ":1,"key":2,"a b\t":3}"#;
+        let map: SerdeMap<String, u8, Trim> = serde_json::from_str(json).unwrap();
+        let keys: Vec<_> = map.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, ["key", "key", "a b"]);
+        assert_eq!(
+            serde_json::to_string(&map).unwrap(),
+            r#"{"key":1,"key":2,"a b":3}"#
+        );
+    }
+
+    #[derive(Debug, Default, PartialEq)]
+    enum Color {
+        Red,
+        #[default]
+        Other,
+    }
+
+    impl FromStr for Color {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "red" => Ok(Self::Red),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl AsRef<str> for Color {
+        fn as_ref(&self) -> &str {
+            match self {
+                Self::Red => "red",
+                Self::Other => "other",
+            }
+        }
+    }
+
+    #[test]
+    fn enum_key() {
+        let json = r#"{"red":1,"blue":2}"#;
+        let map: SerdeMap<String, u8, EnumKey<Color, DefaultUnknown>> =
+            serde_json::from_str(json).unwrap();
+        assert_eq!(map.0, vec![(Color::Red, 1), (Color::Other, 2)]);
+        assert_eq!(
+            serde_json::to_string(&map).unwrap(),
+            r#"{"red":1,"other":2}"#
+        );
+
+        let err = serde_json::from_str::<SerdeMap<String, u8, EnumKey<Color>>>(json)
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with(r#"entry 1: unknown key "blue""#), "{err}");
+    }
+
+    struct BigEndian;
+
+    impl Convert<[u8; 4]> for BigEndian {
+        type Target = u32;
+
+        fn convert<E: Error>(d: [u8; 4]) -> Result<u32, E> {
+            Ok(u32::from_be_bytes(d))
+        }
+
+        fn revert(target: &u32) -> [u8; 4] {
+            target.to_be_bytes()
+        }
+    }
+
+    #[test]
+    fn combinators() {
+        let map: SerdeMap<String, u8, Chain<Trim, DisplayFromStr<i64>>> =
+            serde_json::from_str(r#"{" 42 ":1}"#).unwrap();
+        assert_eq!(map.0, vec![(42, 1)]);
+        assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"42":1}"#);
+
+        let json = r#"{"0000ff01":1}"#;
+        let map: SerdeMap<String, u8, MapWith<Hex<[u8; 4]>, BigEndian>> =
+            serde_json::from_str(json).unwrap();
+        assert_eq!(map.0, vec![(0xff01, 1)]);
+        assert_eq!(serde_json::to_string(&map).unwrap(), json);
+    }
+
+    #[test]
+    fn validated() {
+        type Short = Validated<Trim, MaxLen<3>>;
+        let map: SerdeMap<String, u8, Short> = serde_json::from_str(r#"{" abc ":1}"#).unwrap();
+        assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"abc":1}"#);
+
+        let err = serde_json::from_str::<SerdeMap<String, u8, Short>>(r#"{"a":1,"abcd":2}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with(r#"entry 1: "abcd" is too long: 4 chars, the limit is 3"#),
+            "{err}"
+        );
+    }
+
+    #[cfg(feature = "intern")]
+    #[test]
+    fn interned() {
+        use crate::SerdeMapSeed;
+        use serde::de::DeserializeSeed;
+
+        let interner = Interner::new();
+        let json = r#"{"a":1,"b":2}"#;
+        let mut maps = vec![];
+        for _ in 0..2 {
+            let seed = SerdeMapSeed::<String, u8, _>::new(Interned(interner.clone()), Linear);
+            let mut de = serde_json::Deserializer::from_str(json);
+            maps.push(seed.deserialize(&mut de).unwrap());
+        }
+        assert_eq!(interner.len(), 2);
+        assert!(Arc::ptr_eq(&maps[0].0[0].0, &maps[1].0[0].0));
+        assert_eq!(serde_json::to_string(&maps[0]).unwrap(), json);
+
+        drop(maps.pop());
+        interner.evict_unused();
+        assert_eq!(interner.len(), 2);
+        drop(maps);
+        interner.evict_unused();
+        assert!(interner.is_empty());
+
+        interner.intern("c");
+        interner.clear();
+        assert!(interner.is_empty());
+    }
+
+    #[test]
+    fn from_string() {
+        let json = r#"{"a":1}"#;
+        let map: SerdeMap<String, u8, FromString<::alloc::boxed::Box<str>>> =
+            serde_json::from_str(json).unwrap();
+        assert_eq!(map.get("a"), Some(&1));
+        assert_eq!(serde_json::to_string(&map).unwrap(), json);
+    }
+
+    #[test]
+    fn addr_key() {
+        let json = r#"{"10.0.0.1":1,"::1":2}"#;
+        let map: SerdeMap<String, u8, AddrKey> = serde_json::from_str(json).unwrap();
+        assert_eq!(map.0[1].0, IpAddr::V6(Ipv6Addr::LOCALHOST));
+        assert_eq!(serde_json::to_string(&map).unwrap(), json);
+
+        let json = r#"{"127.0.0.1:80":1}"#;
+        let map: SerdeMap<String, u8, AddrKey<SocketAddr>> = serde_json::from_str(json).unwrap();
+        assert_eq!(map.0[0].0.port(), 80);
+        assert_eq!(serde_json::to_string(&map).unwrap(), json);
+
+        let err = serde_json::from_str::<SerdeMap<String, u8, AddrKey>>(r#"{"x":1}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with(r#"entry 0: invalid address key "x""#),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn bytes_key() {
+        let mut map: SerdeMap<String, ByteBuf, Linear, BytesKey<[u8; 2]>> = SerdeMap::new();
+        map.insert_unchecked("a".into(), [1, 2]);
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#"{"a":[1,2]}"#);
+        let back: SerdeMap<String, ByteBuf, Linear, BytesKey<[u8; 2]>> =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(back.0, map.0);
+        assert!(
+            serde_json::from_str::<SerdeMap<String, ByteBuf, Linear, BytesKey<[u8; 2]>>>(
+                r#"{"a":[1]}"#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn bytes_key_binary() {
+        use crate::tokens::{to_tokens, Token};
+
+        let mut map: SerdeMap<ByteBuf, u8, BytesKey> = SerdeMap::new();
+        map.insert_unchecked(vec![1, 2], 3);
+        assert_eq!(
+            to_tokens(&map).unwrap(),
+            [
+                Token::Map(Some(1)),
+                Token::Bytes(vec![1, 2]),
+                Token::U64(3),
+                Token::End
+            ]
+        );
+    }
+}
@@ -0,0 +1,245 @@
+//! Minimal binary (not human-readable) serializer for the tests, which records the calls
+
+use ::alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use ::core::fmt;
+use serde::ser::{self, Serialize};
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Token {
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    Str(String),
+    Bytes(Vec<u8>),
+    None,
+    Some,
+    Unit,
+    Seq(Option<usize>),
+    Tuple(usize),
+    Map(Option<usize>),
+    End,
+}
+
+#[derive(Debug)]
+pub(crate) struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl ser::StdError for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+pub(crate) fn to_tokens<T: Serialize + ?Sized>(value: &T) -> Result<Vec<Token>, Error> {
+    let mut tokens = Vec::new();
+    value.serialize(Tokens(&mut tokens))?;
+    Ok(tokens)
+}
+
+struct Tokens<'a>(&'a mut Vec<Token>);
+
+impl Tokens<'_> {
+    #[inline]
+    fn push(self, token: Token) -> Result<Self, Error> {
+        self.0.push(token);
+        Ok(self)
+    }
+}
+
+macro_rules! primitive {
+    ($($method:ident($ty:ty) => $token:ident),* $(,)?) => {$(
+        fn $method(self, v: $ty) -> Result<(), Error> {
+            self.push(Token::$token(v.into())).map(drop)
+        }
+    )*};
+}
+
+impl ser::Serializer for Tokens<'_> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    primitive! {
+        serialize_bool(bool) => Bool,
+        serialize_i8(i8) => I64,
+        serialize_i16(i16) => I64,
+        serialize_i32(i32) => I64,
+        serialize_i64(i64) => I64,
+        serialize_u8(u8) => U64,
+        serialize_u16(u16) => U64,
+        serialize_u32(u32) => U64,
+        serialize_u64(u64) => U64,
+        serialize_f32(f32) => F64,
+        serialize_f64(f64) => F64,
+        serialize_str(&str) => Str,
+        serialize_bytes(&[u8]) => Bytes,
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        self.push(Token::Str(v.to_string())).map(drop)
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.push(Token::None).map(drop)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self.push(Token::Some)?)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.push(Token::Unit).map(drop)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &str) -> Result<(), Error> {
+        self.push(Token::Str(variant.into())).map(drop)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self.push(Token::Str(variant.into()))?)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, Error> {
+        self.push(Token::Seq(len))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self, Error> {
+        self.push(Token::Tuple(len))
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, len: usize) -> Result<Self, Error> {
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        len: usize,
+    ) -> Result<Self, Error> {
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self, Error> {
+        self.push(Token::Map(len))
+    }
+
+    fn serialize_struct(self, _: &'static str, len: usize) -> Result<Self, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        len: usize,
+    ) -> Result<Self, Error> {
+        self.serialize_map(Some(len))
+    }
+}
+
+macro_rules! compound {
+    ($($trait:ident::$method:ident),*) => {$(
+        impl ser::$trait for Tokens<'_> {
+            type Ok = ();
+            type Error = Error;
+
+            fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+                value.serialize(Tokens(self.0))
+            }
+
+            fn end(self) -> Result<(), Error> {
+                self.push(Token::End).map(drop)
+            }
+        }
+    )*};
+}
+
+compound!(
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field
+);
+
+impl ser::SerializeMap for Tokens<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        key.serialize(Tokens(self.0))
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(Tokens(self.0))
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.push(Token::End).map(drop)
+    }
+}
+
+macro_rules! fields {
+    ($($trait:ident),*) => {$(
+        impl ser::$trait for Tokens<'_> {
+            type Ok = ();
+            type Error = Error;
+
+            fn serialize_field<T: Serialize + ?Sized>(
+                &mut self,
+                key: &'static str,
+                value: &T,
+            ) -> Result<(), Error> {
+                self.0.push(Token::Str(key.into()));
+                value.serialize(Tokens(self.0))
+            }
+
+            fn end(self) -> Result<(), Error> {
+                self.push(Token::End).map(drop)
+            }
+        }
+    )*};
+}
+
+fields!(SerializeStruct, SerializeStructVariant);
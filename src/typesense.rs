@@ -1,8 +1,647 @@
-use crate::{SerdeMap, SerdeMapStrategy};
+use crate::{BaseStrategy, DeStrategy, Linear, SerStrategy, SerdeMap, SerdeMapRef};
+use ::core::{
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+use ::std::{
+    collections::BTreeSet,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{
+    de::{Deserialize, Deserializer, IgnoredAny, MapAccess, Visitor},
+    ser::{SerializeMap, Serializer},
+    Serialize,
+};
+use serde_json::Value;
+#[cfg(feature = "typesense")]
+use typesense::{models::Field, prelude::ToTypesenseField};
 
-impl<K, V, KS: SerdeMapStrategy<K>> typesense::prelude::ToTypesenseField for SerdeMap<K, V, KS> {
+/// Typesense type name of a field, the same mapping as the `typesense` crate uses,
+/// but without depending on it.
+pub trait TypesenseType {
+    fn typesense_type() -> &'static str;
+}
+
+macro_rules! typesense_type {
+    ($typ:literal: $($t:ty),*) => {$(
+        impl TypesenseType for $t {
+            #[inline(always)]
+            fn typesense_type() -> &'static str {
+                $typ
+            }
+        }
+
+        impl TypesenseType for Vec<$t> {
+            #[inline(always)]
+            fn typesense_type() -> &'static str {
+                concat!($typ, "[]")
+            }
+        }
+
+        impl TypesenseType for Vec<Option<$t>> {
+            #[inline(always)]
+            fn typesense_type() -> &'static str {
+                concat!($typ, "[]")
+            }
+        }
+    )*};
+}
+
+typesense_type!("string": String);
+typesense_type!("int32": i8, u8, i16, u16, i32);
+typesense_type!("int64": u32, i64, u64, isize, usize);
+typesense_type!("float": f32, f64);
+typesense_type!("bool": bool);
+#[cfg(feature = "chrono")]
+typesense_type!("string": ::chrono::NaiveDate, ::chrono::NaiveDateTime, ::chrono::NaiveTime);
+
+#[cfg(feature = "chrono")]
+impl<Tz: ::chrono::TimeZone> TypesenseType for ::chrono::DateTime<Tz> {
     #[inline(always)]
-    fn to_typesense_type() -> &'static str {
+    fn typesense_type() -> &'static str {
+        "string"
+    }
+}
+
+impl<T: TypesenseType> TypesenseType for Option<T> {
+    #[inline(always)]
+    fn typesense_type() -> &'static str {
+        T::typesense_type()
+    }
+}
+
+/// Typesense type of a [`SerdeMap`] field, chosen by the value strategy (`"object"` by default).
+/// A custom value strategy needs an (empty) impl to be used in Typesense documents.
+pub trait TypesenseStrategy<Ser>: BaseStrategy<Ser> {
+    #[inline(always)]
+    fn typesense_type() -> &'static str {
         "object"
     }
+
+    /// Type of `Vec<SerdeMap<..>>`
+    #[inline(always)]
+    fn typesense_array_type() -> &'static str {
+        "object[]"
+    }
+}
+
+impl<Ser> TypesenseStrategy<Ser> for Linear {}
+
+/// Value strategy, which keeps values as is (like [`Linear`]), but reports the Typesense type
+/// of the values, to declare the map as a flattened `"field.*"` field, e.g. `"int32"` for `i32`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Flattened;
+
+impl<Ser> BaseStrategy<Ser> for Flattened {
+    type Des = Ser;
+}
+
+impl<Ser: Serialize> SerStrategy<Ser> for Flattened {
+    type SerRet<'s>
+        = &'s Ser
+    where
+        Ser: 's;
+
+    #[inline(always)]
+    fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+        d
+    }
+}
+
+impl<Ser> DeStrategy<Ser> for Flattened {
+    #[inline(always)]
+    fn deserialize<E>(s: Ser) -> Result<Self::Des, E> {
+        Ok(s)
+    }
+}
+
+impl<Ser: TypesenseType> TypesenseStrategy<Ser> for Flattened
+where
+    Vec<Ser>: TypesenseType,
+{
+    #[inline(always)]
+    fn typesense_type() -> &'static str {
+        Ser::typesense_type()
+    }
+
+    #[inline(always)]
+    fn typesense_array_type() -> &'static str {
+        Vec::<Ser>::typesense_type()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, VS: TypesenseStrategy<V>> TypesenseType for SerdeMap<K, V, KS, VS> {
+    #[inline(always)]
+    fn typesense_type() -> &'static str {
+        VS::typesense_type()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, VS: TypesenseStrategy<V>> TypesenseType
+    for SerdeMapRef<'_, K, V, KS, VS>
+{
+    #[inline(always)]
+    fn typesense_type() -> &'static str {
+        VS::typesense_type()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, VS: TypesenseStrategy<V>> TypesenseType
+    for Vec<SerdeMap<K, V, KS, VS>>
+{
+    #[inline(always)]
+    fn typesense_type() -> &'static str {
+        VS::typesense_array_type()
+    }
+}
+
+impl<K, V, KS: BaseStrategy<K>, VS: TypesenseStrategy<V>> TypesenseType
+    for Vec<SerdeMapRef<'_, K, V, KS, VS>>
+{
+    #[inline(always)]
+    fn typesense_type() -> &'static str {
+        VS::typesense_array_type()
+    }
+}
+
+#[cfg(feature = "typesense")]
+impl<K, V, KS: BaseStrategy<K>, VS: TypesenseStrategy<V>> ToTypesenseField
+    for SerdeMap<K, V, KS, VS>
+{
+    #[inline(always)]
+    fn to_typesense_type() -> &'static str {
+        VS::typesense_type()
+    }
+}
+
+#[cfg(feature = "typesense")]
+impl<K, V, KS: BaseStrategy<K>, VS: TypesenseStrategy<V>> ToTypesenseField
+    for SerdeMapRef<'_, K, V, KS, VS>
+{
+    #[inline(always)]
+    fn to_typesense_type() -> &'static str {
+        VS::typesense_type()
+    }
+}
+
+/// `Vec` of maps for Typesense documents, typed by the value strategy
+/// (`"object[]"` by default), because `ToTypesenseField` can't be implemented for
+/// `Vec<SerdeMap<..>>` outside of the `typesense` crate. (De)serializes as the `Vec`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MapArray<M>(pub Vec<M>);
+
+impl<M> MapArray<M> {
+    #[inline(always)]
+    pub fn into_inner(self) -> Vec<M> {
+        self.0
+    }
+}
+
+impl<M> From<Vec<M>> for MapArray<M> {
+    #[inline(always)]
+    fn from(maps: Vec<M>) -> Self {
+        Self(maps)
+    }
+}
+
+impl<M> FromIterator<M> for MapArray<M> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = M>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<M> Deref for MapArray<M> {
+    type Target = Vec<M>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<M> DerefMut for MapArray<M> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<M: Serialize> Serialize for MapArray<M> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, M: Deserialize<'de>> Deserialize<'de> for MapArray<M> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::deserialize(deserializer).map(Self)
+    }
+}
+
+impl<M> TypesenseType for MapArray<M>
+where
+    Vec<M>: TypesenseType,
+{
+    #[inline(always)]
+    fn typesense_type() -> &'static str {
+        Vec::<M>::typesense_type()
+    }
+}
+
+#[cfg(feature = "typesense")]
+impl<M> ToTypesenseField for MapArray<M>
+where
+    Vec<M>: TypesenseType,
+{
+    #[inline(always)]
+    fn to_typesense_type() -> &'static str {
+        Vec::<M>::typesense_type()
+    }
+}
+
+/// Builds the declarations of the nested `"{prefix}.{key}"` fields of a map field,
+/// which makes them searchable, instead of declaring them one by one.
+#[derive(Debug, Clone, Copy)]
+pub struct NestedFields<'a> {
+    prefix: &'a str,
+    optional: Option<bool>,
+    facet: Option<bool>,
+}
+
+impl<'a> NestedFields<'a> {
+    /// `prefix` is the name of the map field
+    #[inline]
+    pub fn new(prefix: &'a str) -> Self {
+        Self {
+            prefix,
+            optional: None,
+            facet: None,
+        }
+    }
+
+    #[inline]
+    pub fn optional(mut self, optional: bool) -> Self {
+        self.optional = Some(optional);
+        self
+    }
+
+    #[inline]
+    pub fn facet(mut self, facet: bool) -> Self {
+        self.facet = Some(facet);
+        self
+    }
+
+    /// `"{prefix}.{key}"` names, skipping duplicate keys
+    fn names<K: AsRef<str>>(&self, keys: impl IntoIterator<Item = K>) -> Vec<String> {
+        let mut seen = BTreeSet::new();
+        let mut names: Vec<String> = Vec::new();
+        for key in keys {
+            let name = format!("{}.{}", self.prefix, key.as_ref());
+            if seen.insert(name.clone()) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// One field of the type `typ` per key, duplicate keys are skipped
+    #[cfg(feature = "typesense")]
+    pub fn fields<K: AsRef<str>>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+        typ: &str,
+    ) -> Vec<Field> {
+        self.names(keys)
+            .into_iter()
+            .map(|name| {
+                let mut field = Field::new(name, typ.to_string());
+                field.optional = self.optional;
+                field.facet = self.facet;
+                field
+            })
+            .collect()
+    }
+
+    /// One field per key of the map, typed by the values
+    #[cfg(feature = "typesense")]
+    #[inline]
+    pub fn of_map<K, V, KS, VS>(&self, map: &SerdeMap<K, V, KS, VS>) -> Vec<Field>
+    where
+        KS: BaseStrategy<K>,
+        KS::Des: AsRef<str>,
+        VS: BaseStrategy<V>,
+        VS::Des: TypesenseType,
+    {
+        self.fields(map.iter().map(|(k, _)| k), VS::Des::typesense_type())
+    }
+
+    /// Same as `fields`, but as JSON schema fragments, e.g. for the HTTP API
+    pub fn json_fields<K: AsRef<str>>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+        typ: &str,
+    ) -> Vec<Value> {
+        self.names(keys)
+            .into_iter()
+            .map(|name| {
+                let mut field = serde_json::json!({ "name": name, "type": typ });
+                if let Some(optional) = self.optional {
+                    field["optional"] = optional.into();
+                }
+                if let Some(facet) = self.facet {
+                    field["facet"] = facet.into();
+                }
+                field
+            })
+            .collect()
+    }
+
+    /// Same as `of_map`, but as JSON schema fragments
+    #[inline]
+    pub fn json_of_map<K, V, KS, VS>(&self, map: &SerdeMap<K, V, KS, VS>) -> Vec<Value>
+    where
+        KS: BaseStrategy<K>,
+        KS::Des: AsRef<str>,
+        VS: BaseStrategy<V>,
+        VS::Des: TypesenseType,
+    {
+        self.json_fields(map.iter().map(|(k, _)| k), VS::Des::typesense_type())
+    }
+}
+
+/// Name of the map field, which prefixes the flattened document fields
+pub trait FieldPrefix {
+    const PREFIX: &'static str;
+}
+
+/// `#[serde(flatten)]` wrapper, which (de)serializes the entries as top level
+/// `"{P::PREFIX}.{key}"` document fields, so Typesense can facet and sort on them.
+/// Deserialization picks only the fields with the prefix, ignoring the rest.
+pub struct PrefixedFields<P, V, VS: BaseStrategy<V> = Linear>(
+    pub SerdeMap<String, V, Linear, VS>,
+    PhantomData<P>,
+);
+
+impl<P, V, VS: BaseStrategy<V>> PrefixedFields<P, V, VS> {
+    #[inline(always)]
+    pub fn new(map: SerdeMap<String, V, Linear, VS>) -> Self {
+        Self(map, PhantomData)
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> SerdeMap<String, V, Linear, VS> {
+        self.0
+    }
+}
+
+impl<P, V, VS: BaseStrategy<V>> From<SerdeMap<String, V, Linear, VS>> for PrefixedFields<P, V, VS> {
+    #[inline(always)]
+    fn from(map: SerdeMap<String, V, Linear, VS>) -> Self {
+        Self::new(map)
+    }
+}
+
+impl<P, V, VS: BaseStrategy<V>> fmt::Debug for PrefixedFields<P, V, VS>
+where
+    SerdeMap<String, V, Linear, VS>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PrefixedFields").field(&self.0).finish()
+    }
+}
+
+impl<P: FieldPrefix, V, VS: SerStrategy<V>> Serialize for PrefixedFields<P, V, VS> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let human_readable = serializer.is_human_readable();
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        let vs = self.0.value_strategy();
+        for (k, v) in self.0.iter() {
+            let v = vs.serialize_format(v, human_readable)?;
+            map.serialize_entry(&format!("{}.{k}", P::PREFIX), &v)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, P, V, VS> Deserialize<'de> for PrefixedFields<P, V, VS>
+where
+    P: FieldPrefix,
+    V: Deserialize<'de>,
+    VS: DeStrategy<V> + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct PrefixedVisitor<P, V, VS> {
+            human_readable: bool,
+            marker: PhantomData<(P, V, VS)>,
+        }
+
+        impl<'de, P, V, VS> Visitor<'de> for PrefixedVisitor<P, V, VS>
+        where
+            P: FieldPrefix,
+            V: Deserialize<'de>,
+            VS: DeStrategy<V> + Default,
+        {
+            type Value = PrefixedFields<P, V, VS>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut map = SerdeMap::<String, V, Linear, VS>::new();
+                let mut index = 0;
+                while let Some(key) = access.next_key::<String>()? {
+                    let key = key
+                        .strip_prefix(P::PREFIX)
+                        .and_then(|k| k.strip_prefix('.'))
+                        .map(ToString::to_string);
+                    match key {
+                        Some(key) => {
+                            let value = access.next_value()?;
+                            let vs = map.value_strategy();
+                            if let Some(value) =
+                                vs.deserialize_format(index, value, self.human_readable)?
+                            {
+                                map.insert_unchecked(key, value);
+                            }
+                        }
+                        None => {
+                            access.next_value::<IgnoredAny>()?;
+                        }
+                    }
+                    index += 1;
+                }
+                Ok(PrefixedFields::new(map))
+            }
+        }
+
+        let visitor = PrefixedVisitor {
+            human_readable: deserializer.is_human_readable(),
+            marker: PhantomData,
+        };
+        deserializer.deserialize_map(visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::std::string::String;
+
+    #[test]
+    fn raw_types() {
+        assert_eq!(Option::<u32>::typesense_type(), "int64");
+        assert_eq!(Vec::<String>::typesense_type(), "string[]");
+        assert_eq!(SerdeMap::<String, i32>::typesense_type(), "object");
+        assert_eq!(Vec::<SerdeMap<String, i32>>::typesense_type(), "object[]");
+        assert_eq!(
+            Vec::<SerdeMap<String, i32, Linear, Flattened>>::typesense_type(),
+            "int32[]"
+        );
+
+        let map: SerdeMap<String, bool> = SerdeMap::from_iter([("a".into(), true)]);
+        let fields = NestedFields::new("flags").optional(true).json_of_map(&map);
+        assert_eq!(
+            fields,
+            [serde_json::json!({ "name": "flags.a", "type": "bool", "optional": true })]
+        );
+    }
+
+    #[cfg(feature = "typesense")]
+    #[test]
+    fn typesense_types() {
+        assert_eq!(SerdeMap::<String, i32>::to_typesense_type(), "object");
+        assert_eq!(SerdeMapRef::<String, i32>::to_typesense_type(), "object");
+        assert_eq!(
+            Option::<SerdeMap<String, i32, Linear, Flattened>>::to_typesense_type(),
+            "int32"
+        );
+    }
+
+    #[cfg(feature = "typesense")]
+    #[test]
+    fn typesense_array_types() {
+        fn field_type<T: ::typesense::prelude::ToTypesenseField>() -> &'static str {
+            T::to_typesense_type()
+        }
+
+        assert_eq!(field_type::<MapArray<SerdeMap<String, i32>>>(), "object[]");
+        assert_eq!(
+            field_type::<MapArray<SerdeMap<String, i32, Linear, Flattened>>>(),
+            "int32[]"
+        );
+        assert_eq!(
+            field_type::<MapArray<SerdeMapRef<String, bool, Linear, Flattened>>>(),
+            "bool[]"
+        );
+        assert_eq!(
+            field_type::<Option<MapArray<SerdeMap<String, i32>>>>(),
+            "object[]"
+        );
+
+        let maps: MapArray<SerdeMap<String, i32>> =
+            MapArray::from_iter([SerdeMap::from_iter([("a".into(), 1)])]);
+        let json = serde_json::to_string(&maps).unwrap();
+        assert_eq!(json, r#"[{"a":1}]"#);
+        let back: MapArray<SerdeMap<String, i32>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back[0].0, maps[0].0);
+    }
+
+    #[cfg(feature = "typesense")]
+    #[test]
+    fn nested_fields() {
+        let map: SerdeMap<String, i32> =
+            SerdeMap::from_iter([("a".into(), 1), ("b".into(), 2), ("a".into(), 3)]);
+        let fields = NestedFields::new("stats").facet(true).of_map(&map);
+        let names: Vec<_> = fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["stats.a", "stats.b"]);
+        assert!(fields
+            .iter()
+            .all(|f| f.r#type == "int32" && f.facet == Some(true) && f.optional.is_none()));
+
+        let fields = NestedFields::new("tags")
+            .optional(true)
+            .fields(["x"], "string");
+        assert_eq!(fields[0].name, "tags.x");
+        assert_eq!(fields[0].optional, Some(true));
+    }
+
+    #[test]
+    fn prefixed_fields() {
+        struct Stats;
+
+        impl FieldPrefix for Stats {
+            const PREFIX: &'static str = "stats";
+        }
+
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Document {
+            id: String,
+            #[serde(flatten)]
+            stats: PrefixedFields<Stats, i32>,
+        }
+
+        let doc = Document {
+            id: "1".into(),
+            stats: PrefixedFields::new(SerdeMap::from_iter([("a".into(), 1), ("b".into(), 2)])),
+        };
+        let json = serde_json::to_string(&doc).unwrap();
+        assert_eq!(json, r#"{"id":"1","stats.a":1,"stats.b":2}"#);
+
+        let json = r#"{"id":"1","stats.a":1,"statsx":"?","other.b":"?","stats.b":2}"#;
+        let back: Document = serde_json::from_str(json).unwrap();
+        assert_eq!(back.stats.0 .0, doc.stats.0 .0);
+    }
+
+    #[test]
+    fn prefixed_fields_filter() {
+        use serde::de::Error;
+
+        struct Stats;
+
+        impl FieldPrefix for Stats {
+            const PREFIX: &'static str = "stats";
+        }
+
+        #[derive(Default)]
+        struct Positive;
+
+        impl BaseStrategy<i32> for Positive {
+            type Des = i32;
+        }
+
+        impl DeStrategy<i32> for Positive {
+            fn deserialize<E: Error>(s: i32) -> Result<Self::Des, E> {
+                Ok(s)
+            }
+            fn deserialize_filter<E: Error>(&self, _: usize, s: i32) -> Result<Option<i32>, E> {
+                Ok(Some(s).filter(|s| *s > 0))
+            }
+        }
+
+        let json = r#"{"stats.a":-1,"stats.b":2}"#;
+        let fields: PrefixedFields<Stats, i32, Positive> = serde_json::from_str(json).unwrap();
+        assert_eq!(fields.0 .0, [("b".into(), 2)]);
+    }
 }
@@ -0,0 +1,48 @@
+use crate::{BaseStrategy, SerdeMap};
+use ::std::string::String;
+use serde::{Deserialize, Serialize};
+
+impl<K, V, KS: BaseStrategy<K>, VS: BaseStrategy<V>> SerdeMap<K, V, KS, VS> {
+    /// Parses `a=1&b=2` (without the leading `?`), keeping the order and the repeated keys
+    #[inline]
+    pub fn from_query_str<'de>(s: &'de str) -> Result<Self, serde_urlencoded::de::Error>
+    where
+        Self: Deserialize<'de>,
+    {
+        serde_urlencoded::from_str(s)
+    }
+
+    /// Encodes the entries in their order, the repeated keys are written as is
+    #[inline]
+    pub fn to_query_string(&self) -> Result<String, serde_urlencoded::ser::Error>
+    where
+        Self: Serialize,
+    {
+        serde_urlencoded::to_string(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::std::vec;
+
+    #[test]
+    fn query_string() {
+        let query = "b=1&a=x+y&b=%26";
+        let map = SerdeMap::<String, String>::from_query_str(query).unwrap();
+        assert_eq!(
+            map.0,
+            vec![
+                ("b".into(), "1".into()),
+                ("a".into(), "x y".into()),
+                ("b".into(), "&".into()),
+            ]
+        );
+        assert_eq!(map.to_query_string().unwrap(), "b=1&a=x+y&b=%26");
+
+        let map = SerdeMap::<String, u8, crate::IntKey<u8>>::from_query_str("2=3&1=4").unwrap();
+        assert_eq!(map.0, vec![(2, 3), (1, 4)]);
+        assert_eq!(map.to_query_string().unwrap(), "2=3&1=4");
+    }
+}
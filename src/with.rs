@@ -0,0 +1,273 @@
+//! Helpers for `#[serde(with = ...)]`. The map modules (de)serialize std maps using
+//! a [`SerdeMapStrategy`](crate::SerdeMapStrategy), so fields can keep their map type:
+//! ```rust
+//! # use serde::de::Error;
+//! # use serde_map::{BaseStrategy, DeStrategy, SerStrategy};
+//! # #[derive(Default)]
+//! # struct StringStrategy;
+//! # impl BaseStrategy<String> for StringStrategy {
+//! #     type Des = i64;
+//! # }
+//! # impl SerStrategy<String> for StringStrategy {
+//! #     type SerRet<'s> = String;
+//! #     fn serialize(d: &Self::Des) -> Self::SerRet<'_> {
+//! #         d.to_string()
+//! #     }
+//! # }
+//! # impl DeStrategy<String> for StringStrategy {
+//! #     fn deserialize<E: Error>(s: String) -> Result<Self::Des, E> {
+//! #         s.parse().map_err(Error::custom)
+//! #     }
+//! # }
+//! use std::collections::BTreeMap;
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Data {
+//!     #[serde(with = "serde_map::with::btree_map::Strategy::<String, StringStrategy>")]
+//!     ints: BTreeMap<i64, u8>,
+//! }
+//! ```
+//! Entries are deserialized in the input order, so the last duplicate key wins.
+//! The strategies are applied as `KS::default()` instances.
+
+/// `#[serde(default, with = "serde_map::with::option")]` for `Option<SerdeMap<..>>` fields.
+/// Empty maps are treated as absent: `Some(empty)` is serialized as `null`,
+/// and both `null` and `{}` are deserialized as `None`.
+pub mod option {
+    use crate::{BaseStrategy, SerdeMap};
+    use serde::{
+        de::{Deserialize, Deserializer},
+        ser::{Serialize, Serializer},
+    };
+
+    type OptionMap<K, V, KS, VS> = Option<SerdeMap<K, V, KS, VS>>;
+
+    #[inline]
+    pub fn serialize<K, V, KS, VS, S>(
+        map: &OptionMap<K, V, KS, VS>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        KS: BaseStrategy<K>,
+        VS: BaseStrategy<V>,
+        SerdeMap<K, V, KS, VS>: Serialize,
+        S: Serializer,
+    {
+        match map {
+            Some(map) if !map.is_empty() => serializer.serialize_some(map),
+            _ => serializer.serialize_none(),
+        }
+    }
+
+    #[inline]
+    pub fn deserialize<'de, K, V, KS, VS, D>(
+        deserializer: D,
+    ) -> Result<OptionMap<K, V, KS, VS>, D::Error>
+    where
+        KS: BaseStrategy<K>,
+        VS: BaseStrategy<V>,
+        SerdeMap<K, V, KS, VS>: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        OptionMap::<K, V, KS, VS>::deserialize(deserializer)
+            .map(|map| map.filter(|map| !map.is_empty()))
+    }
+}
+
+/// `#[serde(with = "serde_map::with::btree_map::Strategy::<K, KS>")]` for `BTreeMap<KS::Des, V>`
+pub mod btree_map {
+    use crate::{BaseStrategy, DeStrategy, Entries, Linear, SerStrategy};
+    use ::alloc::collections::BTreeMap;
+    use ::core::marker::PhantomData;
+    use serde::{
+        de::{Deserialize, Deserializer},
+        ser::{Serialize, Serializer},
+    };
+
+    /// Key `K` is (de)serialized through the strategy `KS`
+    pub struct Strategy<K, KS: BaseStrategy<K> = Linear>(PhantomData<(K, KS)>);
+
+    impl<K, KS: BaseStrategy<K>> Strategy<K, KS> {
+        #[inline]
+        pub fn serialize<V: Serialize, S: Serializer>(
+            map: &BTreeMap<KS::Des, V>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
+        where
+            KS: SerStrategy<K> + Default,
+        {
+            crate::serialize_iter::<K, V, KS, _, _>(map.iter(), serializer)
+        }
+
+        #[inline]
+        pub fn deserialize<'de, V, D>(deserializer: D) -> Result<BTreeMap<KS::Des, V>, D::Error>
+        where
+            K: Deserialize<'de>,
+            V: Deserialize<'de>,
+            KS: DeStrategy<K> + Default,
+            KS::Des: Ord,
+            D: Deserializer<'de>,
+        {
+            Entries::<K, V, KS>::deserialize(deserializer).map(|e| e.0.into_iter().collect())
+        }
+    }
+}
+
+/// `#[serde(with = "serde_map::with::vec::Strategy::<K, KS>")]` for `Vec<(KS::Des, V)>`,
+/// which is (de)serialized as a map, keeping the order and duplicates
+pub mod vec {
+    use crate::{BaseStrategy, DeStrategy, Entries, Linear, SerStrategy};
+    use ::alloc::vec::Vec;
+    use ::core::marker::PhantomData;
+    use serde::{
+        de::{Deserialize, Deserializer},
+        ser::{Serialize, Serializer},
+    };
+
+    /// Key `K` is (de)serialized through the strategy `KS`
+    pub struct Strategy<K, KS: BaseStrategy<K> = Linear>(PhantomData<(K, KS)>);
+
+    impl<K, KS: BaseStrategy<K>> Strategy<K, KS> {
+        #[inline]
+        pub fn serialize<V: Serialize, S: Serializer>(
+            entries: &[(KS::Des, V)],
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
+        where
+            KS: SerStrategy<K> + Default,
+        {
+            crate::serialize_iter::<K, V, KS, _, _>(entries.iter().map(|(k, v)| (k, v)), serializer)
+        }
+
+        #[inline]
+        pub fn deserialize<'de, V, D>(deserializer: D) -> Result<Vec<(KS::Des, V)>, D::Error>
+        where
+            K: Deserialize<'de>,
+            V: Deserialize<'de>,
+            KS: DeStrategy<K> + Default,
+            D: Deserializer<'de>,
+        {
+            Entries::<K, V, KS>::deserialize(deserializer).map(|e| e.0)
+        }
+    }
+}
+
+/// `#[serde(with = "serde_map::with::hash_map::Strategy::<K, KS>")]` for `HashMap<KS::Des, V, H>`
+#[cfg(feature = "std")]
+pub mod hash_map {
+    use crate::{BaseStrategy, DeStrategy, Entries, Linear, SerStrategy};
+    use ::core::{
+        hash::{BuildHasher, Hash},
+        marker::PhantomData,
+    };
+    use ::std::collections::HashMap;
+    use serde::{
+        de::{Deserialize, Deserializer},
+        ser::{Serialize, Serializer},
+    };
+
+    /// Key `K` is (de)serialized through the strategy `KS`
+    pub struct Strategy<K, KS: BaseStrategy<K> = Linear>(PhantomData<(K, KS)>);
+
+    impl<K, KS: BaseStrategy<K>> Strategy<K, KS> {
+        #[inline]
+        pub fn serialize<V: Serialize, H, S: Serializer>(
+            map: &HashMap<KS::Des, V, H>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
+        where
+            KS: SerStrategy<K> + Default,
+        {
+            crate::serialize_iter::<K, V, KS, _, _>(map.iter(), serializer)
+        }
+
+        #[inline]
+        pub fn deserialize<'de, V, H, D>(
+            deserializer: D,
+        ) -> Result<HashMap<KS::Des, V, H>, D::Error>
+        where
+            K: Deserialize<'de>,
+            V: Deserialize<'de>,
+            KS: DeStrategy<K> + Default,
+            KS::Des: Eq + Hash,
+            H: BuildHasher + Default,
+            D: Deserializer<'de>,
+        {
+            Entries::<K, V, KS>::deserialize(deserializer).map(|e| e.0.into_iter().collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IntKey;
+    use ::alloc::{collections::BTreeMap, string::String};
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Data {
+        #[serde(with = "btree_map::Strategy::<String, IntKey<i64>>")]
+        ints: BTreeMap<i64, u8>,
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Pairs {
+        #[serde(with = "vec::Strategy::<String, IntKey<i64>>")]
+        ints: ::alloc::vec::Vec<(i64, u8)>,
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct OptionalData {
+        #[serde(default, with = "option")]
+        map: Option<crate::SerdeMap<String, u8>>,
+    }
+
+    #[test]
+    fn with_option() {
+        for json in [r#"{}"#, r#"{"map":null}"#, r#"{"map":{}}"#] {
+            let data: OptionalData = serde_json::from_str(json).unwrap();
+            assert!(data.map.is_none());
+        }
+        let data: OptionalData = serde_json::from_str(r#"{"map":{"a":1}}"#).unwrap();
+        assert_eq!(data.map.as_ref().map(|m| m.len()), Some(1));
+
+        let empty = OptionalData {
+            map: Some(Default::default()),
+        };
+        assert_eq!(serde_json::to_string(&empty).unwrap(), r#"{"map":null}"#);
+    }
+
+    #[test]
+    fn with_btree_map() {
+        let json = r#"{"ints":{"1":2,"3":4}}"#;
+        let data: Data = serde_json::from_str(json).unwrap();
+        assert_eq!(data.ints, BTreeMap::from([(1, 2), (3, 4)]));
+        assert_eq!(serde_json::to_string(&data).unwrap(), json);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn with_hash_map() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Hashed {
+            #[serde(with = "hash_map::Strategy::<String, IntKey<i64>>")]
+            ints: ::std::collections::HashMap<i64, u8>,
+        }
+
+        let data: Hashed = serde_json::from_str(r#"{"ints":{"1":2,"3":4,"1":5}}"#).unwrap();
+        assert_eq!(data.ints, [(1, 5), (3, 4)].into_iter().collect());
+        let json = serde_json::to_string(&data).unwrap();
+        assert!(
+            json == r#"{"ints":{"1":5,"3":4}}"# || json == r#"{"ints":{"3":4,"1":5}}"#,
+            "{json}"
+        );
+    }
+
+    #[test]
+    fn with_vec() {
+        let json = r#"{"ints":{"3":4,"1":2,"3":5}}"#;
+        let data: Pairs = serde_json::from_str(json).unwrap();
+        assert_eq!(data.ints, [(3, 4), (1, 2), (3, 5)]);
+        assert_eq!(serde_json::to_string(&data).unwrap(), json);
+    }
+}